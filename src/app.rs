@@ -1,23 +1,152 @@
-use crate::types::{Attendance, Day, Expense, Person};
-use currency_rs::{Currency, CurrencyOpts};
-use egui::{
-    epaint::{Color32, Stroke},
-    RichText, Rounding, Vec2,
+use crate::charts;
+use crate::currency::{find_rate, parse_amount, CurrencySettings, ExchangeRate};
+use crate::diet::{is_compatible, meals_without_compatible_food, DIETARY_TAGS};
+use crate::i18n::{day_label, format_number, format_short_date, set_language, tr, trf, Language};
+use crate::meal_plan::{format_quantity, shopping_list, shopping_list_text, MenuItem};
+use crate::receipt::{self, PickedFiles, ReceiptImages};
+use crate::recipes::{find_recipe, Ingredient, Recipe};
+use crate::report::{Report, ReportFormat};
+use crate::theme::{Accent, Palette, Theme, ThemeMode};
+use crate::types::{
+    Attendance, Category, Contribution, Day, DayCost, Expense, Fractions, Person, ServingCosts,
+    ServingSplit, ServingType, Servings, Stay,
 };
+use chrono::{Duration, Local, NaiveDate};
+use egui::{Key, KeyboardShortcut, Modifiers, RichText, Rounding, Vec2};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::ops::RangeInclusive;
 
+const NARROW_SCREEN_WIDTH: f32 = 800.0;
+
+const NEW_PERSON_FIELD: &str = "new-person-name";
+const NEW_EXPENSE_FIELD: &str = "new-expense-name";
+const FOCUS_PERSON_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::ALT, Key::P);
+const FOCUS_EXPENSE_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::ALT, Key::E);
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+enum Section {
+    #[default]
+    Trip,
+    Balances,
+    People,
+    Expenses,
+}
+
+impl Section {
+    const ALL: [Section; 4] = [
+        Section::Trip,
+        Section::Balances,
+        Section::People,
+        Section::Expenses,
+    ];
+
+    fn name(&self) -> &'static str {
+        tr(match self {
+            Section::Trip => "Trip",
+            Section::Balances => "Balances",
+            Section::People => "People",
+            Section::Expenses => "Expenses",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+enum ExpenseSort {
+    #[default]
+    Added,
+    Date,
+    Name,
+    Price,
+}
+
+impl ExpenseSort {
+    const ALL: [ExpenseSort; 4] = [
+        ExpenseSort::Added,
+        ExpenseSort::Date,
+        ExpenseSort::Name,
+        ExpenseSort::Price,
+    ];
+
+    fn name(&self) -> &'static str {
+        tr(match self {
+            ExpenseSort::Added => "Date added",
+            ExpenseSort::Date => "Date",
+            ExpenseSort::Name => "Name",
+            ExpenseSort::Price => "Price",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+enum PersonSort {
+    #[default]
+    Added,
+    Name,
+    Cost,
+}
+
+impl PersonSort {
+    const ALL: [PersonSort; 3] = [PersonSort::Added, PersonSort::Name, PersonSort::Cost];
+
+    fn name(&self) -> &'static str {
+        tr(match self {
+            PersonSort::Added => "Date added",
+            PersonSort::Name => "Name",
+            PersonSort::Cost => "Cost",
+        })
+    }
+}
+
+enum AttendanceAction {
+    EveryoneAttendsAll,
+    AttendAll(usize),
+    CopyFrom { from: usize, to: usize },
+    ClearDay(usize),
+}
+
 #[derive(Deserialize, Serialize)]
 #[serde(default)]
 pub struct MoekkiCalcApp {
+    language: Language,
+    theme: Theme,
+    currency: CurrencySettings,
+    exchange_rates: Vec<ExchangeRate>,
     #[serde(skip)]
-    currency_opts_eur: CurrencyOpts,
+    new_exchange_rate_code: String,
+    #[serde(skip)]
+    exchange_rates_to_remove: Vec<usize>,
 
     expenses: Vec<Expense>,
+    next_expense_id: u64,
     #[serde(skip)]
     new_expense_name: String,
     #[serde(skip)]
-    new_expense_price: f64,
+    new_expense_price: String,
+    #[serde(skip)]
+    new_expense_servings: Servings,
+    #[serde(skip)]
+    new_expense_currency: String,
+    #[serde(skip)]
+    new_expense_estimate: bool,
+    #[serde(skip)]
+    new_expense_credit: bool,
+    #[serde(skip)]
+    new_expense_category: Category,
+    #[serde(skip)]
+    expense_search: String,
+    #[serde(skip)]
+    expense_sort: ExpenseSort,
+    #[serde(skip)]
+    expense_sort_descending: bool,
+    #[serde(skip)]
+    expense_category_filter: Option<Category>,
+    #[serde(skip)]
+    expense_serving_filter: Option<ServingType>,
+    #[serde(skip)]
+    expense_payer_filter: Option<String>,
+    #[serde(skip)]
+    expense_tag_filter: String,
     #[serde(skip)]
     expenses_to_remove: Vec<usize>,
 
@@ -26,45 +155,162 @@ pub struct MoekkiCalcApp {
     total_dinner_cost: f64,
     total_snacks_cost: f64,
     total_cost: f64,
+    estimated_costs: ServingCosts,
 
+    trip_start_date: NaiveDate,
     days: Vec<Day>,
     #[serde(skip)]
     days_to_remove: Vec<usize>,
     people: Vec<Person>,
+    attendance_table: bool,
+    section: Section,
+    #[serde(skip)]
+    attendance_drag_value: Option<bool>,
+    #[serde(skip)]
+    attendance_actions: Vec<AttendanceAction>,
+    #[serde(skip)]
+    clear_day_idx: usize,
     #[serde(skip)]
     new_person_name: String,
     #[serde(skip)]
+    person_search: String,
+    #[serde(skip)]
+    person_sort: PersonSort,
+    #[serde(skip)]
+    person_sort_descending: bool,
+    #[serde(skip)]
     people_to_remove: Vec<usize>,
 
+    report_format: ReportFormat,
+    #[serde(skip)]
+    report_text: String,
+    #[serde(skip)]
+    show_report: bool,
+
+    #[serde(skip)]
+    show_charts: bool,
+    #[serde(skip)]
+    show_ledger: bool,
+
+    #[serde(skip)]
+    picked_receipts: PickedFiles,
+    #[serde(skip)]
+    receipt_images: ReceiptImages,
+    #[serde(skip)]
+    receipt_view: Option<(u64, egui::TextureHandle)>,
+    #[serde(skip)]
+    receipt_error: Option<(u64, String)>,
+
+    #[serde(skip)]
+    show_meal_plan: bool,
+    #[serde(skip)]
+    new_menu_item_day: usize,
+    #[serde(skip)]
+    new_menu_item_serving: ServingType,
+    #[serde(skip)]
+    new_menu_item_name: String,
+    #[serde(skip)]
+    new_menu_item_quantity: f64,
+    #[serde(skip)]
+    new_menu_item_unit: String,
+    #[serde(skip)]
+    menu_items_to_remove: Vec<(usize, usize)>,
+    #[serde(skip)]
+    new_menu_recipe: String,
+
+    recipes: Vec<Recipe>,
+    #[serde(skip)]
+    show_recipes: bool,
+    #[serde(skip)]
+    new_recipe_name: String,
+    #[serde(skip)]
+    recipes_to_remove: Vec<usize>,
+    #[serde(skip)]
+    ingredients_to_remove: Vec<(usize, usize)>,
+
     #[serde(skip)]
     update_attendances: bool,
     #[serde(skip)]
     update_costs: bool,
+    #[serde(skip)]
+    unassigned_contribution: f64,
+    /// Expenses whose servings nobody eats, so nobody is charged for them.
+    #[serde(skip)]
+    uncharged_expenses: HashSet<u64>,
 }
 
 impl Default for MoekkiCalcApp {
     fn default() -> Self {
         Self {
-            currency_opts_eur: CurrencyOpts::new()
-                .set_pattern("#!")
-                .set_symbol("€")
-                .set_separator(","),
+            language: Language::default(),
+            theme: Theme::default(),
+            currency: CurrencySettings::default(),
+            exchange_rates: Vec::new(),
+            new_exchange_rate_code: String::new(),
+            exchange_rates_to_remove: Vec::new(),
             expenses: Vec::new(),
+            next_expense_id: 1,
             new_expense_name: String::new(),
-            new_expense_price: 0.0,
+            new_expense_price: String::new(),
+            new_expense_servings: Servings::default(),
+            new_expense_currency: String::new(),
+            new_expense_estimate: false,
+            new_expense_credit: false,
+            new_expense_category: Category::default(),
+            expense_search: String::new(),
+            expense_sort: ExpenseSort::default(),
+            expense_sort_descending: false,
+            expense_category_filter: None,
+            expense_serving_filter: None,
+            expense_payer_filter: None,
+            expense_tag_filter: String::new(),
             total_breakfast_cost: 0.0,
             total_lunch_cost: 0.0,
             total_dinner_cost: 0.0,
             total_snacks_cost: 0.0,
             total_cost: 0.0,
+            estimated_costs: ServingCosts::default(),
+            trip_start_date: Local::now().date_naive(),
             days: Vec::new(),
             days_to_remove: Vec::new(),
             expenses_to_remove: Vec::new(),
             people: Vec::new(),
+            attendance_table: false,
+            section: Section::default(),
+            attendance_drag_value: None,
+            attendance_actions: Vec::new(),
+            clear_day_idx: 0,
             new_person_name: String::new(),
+            person_search: String::new(),
+            person_sort: PersonSort::default(),
+            person_sort_descending: false,
             people_to_remove: Vec::new(),
+            report_format: ReportFormat::default(),
+            report_text: String::new(),
+            show_report: false,
+            show_charts: false,
+            show_ledger: false,
+            picked_receipts: PickedFiles::default(),
+            receipt_images: ReceiptImages::default(),
+            receipt_view: None,
+            receipt_error: None,
+            show_meal_plan: false,
+            new_menu_item_day: 0,
+            new_menu_item_serving: ServingType::Dinner,
+            new_menu_item_name: String::new(),
+            new_menu_item_quantity: 0.0,
+            new_menu_item_unit: String::new(),
+            menu_items_to_remove: Vec::new(),
+            new_menu_recipe: String::new(),
+            recipes: Vec::new(),
+            show_recipes: false,
+            new_recipe_name: String::new(),
+            recipes_to_remove: Vec::new(),
+            ingredients_to_remove: Vec::new(),
             update_attendances: false,
             update_costs: false,
+            unassigned_contribution: 0.0,
+            uncharged_expenses: HashSet::new(),
         }
     }
 }
@@ -72,54 +318,102 @@ impl Default for MoekkiCalcApp {
 impl MoekkiCalcApp {
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
         if let Some(storage) = cc.storage {
-            return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
+            let mut app: Self = eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
+            app.assign_expense_ids();
+            for e in app.expenses.iter().filter(|x| x.receipt.is_some()) {
+                app.receipt_images.load(storage, e.id);
+            }
+            set_language(app.language);
+            app.update_attendances = true;
+            app.update_costs = true;
+            return app;
         }
 
         Default::default()
     }
 
-    fn update_costs(&mut self) {
-        let mut total = 0.0;
-        let mut total_breakfast = 0.0;
-        let mut total_lunch = 0.0;
-        let mut total_dinner = 0.0;
-        let mut total_snacks = 0.0;
-        for e in self.expenses.iter() {
-            total += e.price;
-            let mut divided = 0.0;
-            if e.serving_type.breakfast {
-                divided += 1.0;
-            }
-            if e.serving_type.lunch {
-                divided += 1.0;
-            }
-            if e.serving_type.dinner {
-                divided += 1.0;
-            }
-            if e.serving_type.snacks {
-                divided += 1.0;
-            }
+    /// Gives ids to expenses saved without one and keeps new ids clear of the loaded ones.
+    fn assign_expense_ids(&mut self) {
+        let max_id = self.expenses.iter().map(|x| x.id).max().unwrap_or_default();
+        self.next_expense_id = self.next_expense_id.max(max_id + 1);
+        for e in self.expenses.iter_mut().filter(|x| x.id == 0) {
+            e.id = self.next_expense_id;
+            self.next_expense_id += 1;
+        }
+    }
 
-            let divided_price = e.price / divided;
-            if e.serving_type.breakfast {
-                total_breakfast += divided_price;
-            }
-            if e.serving_type.lunch {
-                total_lunch += divided_price;
+    fn new_expense_id(&mut self) -> u64 {
+        let id = self.next_expense_id;
+        self.next_expense_id += 1;
+        id
+    }
+
+    fn update_costs(&mut self) {
+        let mut totals = ServingCosts::default();
+        let mut estimated_totals = ServingCosts::default();
+        let mut actual = ServingCosts::default();
+        let mut estimated = ServingCosts::default();
+        let mut actual_by_day: Vec<ServingCosts> =
+            self.days.iter().map(|_| ServingCosts::default()).collect();
+        let mut estimated_by_day: Vec<ServingCosts> =
+            self.days.iter().map(|_| ServingCosts::default()).collect();
+        let mut restricted: Vec<(usize, Option<usize>, Servings)> = Vec::new();
+        self.uncharged_expenses.clear();
+        for (idx, e) in self.expenses.iter_mut().enumerate() {
+            let target_day = e.target_day_index(&self.days, self.trip_start_date);
+            if let Some(recipe) = e
+                .recipe
+                .as_ref()
+                .and_then(|x| find_recipe(&self.recipes, x))
+            {
+                let portions: f64 = match target_day {
+                    Some(idx) => ServingType::ALL
+                        .iter()
+                        .filter(|x| e.serving_type.get(**x))
+                        .map(|x| self.days[idx].attendance_weight(*x))
+                        .sum(),
+                    None => 0.0,
+                };
+                e.price = recipe.cost_per_portion() * portions;
             }
-            if e.serving_type.dinner {
-                total_dinner += divided_price;
+            e.base_price = self
+                .currency
+                .convert(e.signed_price(), &e.currency, &self.exchange_rates)
+                .unwrap_or(0.0);
+            if e.estimate {
+                estimated_totals.add(e.base_price, &e.serving_type, &e.split);
+            } else {
+                totals.add(e.base_price, &e.serving_type, &e.split);
             }
-            if e.serving_type.snacks {
-                total_snacks += divided_price;
+            let charged_days = match target_day {
+                Some(idx) => &self.days[idx..=idx],
+                None => &self.days[..],
+            };
+            let Some(servings) = e.charged_servings(charged_days) else {
+                if e.base_price != 0.0 {
+                    self.uncharged_expenses.insert(e.id);
+                }
+                continue;
+            };
+            if e.exclude_incompatible {
+                restricted.push((idx, target_day, servings));
+                continue;
             }
+            let costs = match (e.estimate, target_day) {
+                (false, None) => &mut actual,
+                (true, None) => &mut estimated,
+                (false, Some(idx)) => &mut actual_by_day[idx],
+                (true, Some(idx)) => &mut estimated_by_day[idx],
+            };
+            costs.add(e.base_price, &servings, &e.split);
         }
 
-        self.total_cost = total;
-        self.total_breakfast_cost = total_breakfast;
-        self.total_lunch_cost = total_lunch;
-        self.total_dinner_cost = total_dinner;
-        self.total_snacks_cost = total_snacks;
+        self.total_cost = totals.total;
+        self.total_breakfast_cost = totals.breakfast;
+        self.total_lunch_cost = totals.lunch;
+        self.total_dinner_cost = totals.dinner;
+        self.total_snacks_cost = totals.snacks;
+        self.estimated_costs = estimated_totals;
 
         let breakfast_divided = self.days.iter().filter(|x| x.servings.breakfast).count();
 
@@ -129,56 +423,214 @@ impl MoekkiCalcApp {
 
         let snacks_divided = self.days.iter().filter(|x| x.servings.snacks).count();
 
-        for d in self.days.iter_mut() {
+        for (idx, d) in self.days.iter_mut().enumerate() {
+            let day_actual = &actual_by_day[idx];
+            let day_estimated = &estimated_by_day[idx];
+            d.estimated_rates = ServingCosts::default();
             if d.servings.breakfast {
-                d.breakfast_day_rate = self.total_breakfast_cost / breakfast_divided as f64;
+                d.breakfast_day_rate =
+                    actual.breakfast / breakfast_divided as f64 + day_actual.breakfast;
+                d.estimated_rates.breakfast =
+                    estimated.breakfast / breakfast_divided as f64 + day_estimated.breakfast;
             } else {
                 d.breakfast_day_rate = 0.0;
             }
             if d.servings.lunch {
-                d.lunch_day_rate = self.total_lunch_cost / lunch_divided as f64;
+                d.lunch_day_rate = actual.lunch / lunch_divided as f64 + day_actual.lunch;
+                d.estimated_rates.lunch =
+                    estimated.lunch / lunch_divided as f64 + day_estimated.lunch;
             } else {
                 d.lunch_day_rate = 0.0;
             }
             if d.servings.dinner {
-                d.dinner_day_rate = self.total_dinner_cost / dinner_divided as f64;
+                d.dinner_day_rate = actual.dinner / dinner_divided as f64 + day_actual.dinner;
+                d.estimated_rates.dinner =
+                    estimated.dinner / dinner_divided as f64 + day_estimated.dinner;
             } else {
                 d.dinner_day_rate = 0.0;
             }
             if d.servings.snacks {
-                d.snacks_day_rate = self.total_snacks_cost / snacks_divided as f64;
+                d.snacks_day_rate = actual.snacks / snacks_divided as f64 + day_actual.snacks;
+                d.estimated_rates.snacks =
+                    estimated.snacks / snacks_divided as f64 + day_estimated.snacks;
             } else {
                 d.snacks_day_rate = 0.0;
             }
             d.total_day_rate =
                 d.breakfast_day_rate + d.lunch_day_rate + d.dinner_day_rate + d.snacks_day_rate;
+            d.estimated_rates.total = d.estimated_rates.breakfast
+                + d.estimated_rates.lunch
+                + d.estimated_rates.dinner
+                + d.estimated_rates.snacks;
         }
 
         for p in self.people.iter_mut() {
             let mut total_cost = 0.0;
+            let mut estimated_cost = 0.0;
+            p.breakdown.clear();
             for (idx, a) in p.attendance.iter().enumerate() {
+                let day = self.days.get(idx).unwrap();
+                let mut day_cost = DayCost::new(day.name.clone());
                 if a.present {
-                    let day = self.days.get(idx).unwrap();
                     if a.servings.breakfast {
-                        total_cost +=
-                            day.breakfast_day_rate / day.breakfast_attendance_count as f64;
+                        let share =
+                            a.weight(ServingType::Breakfast) / day.breakfast_attendance_weight;
+                        day_cost.breakfast = day.breakfast_day_rate * share;
+                        estimated_cost += day.estimated_rates.breakfast * share;
                     }
                     if a.servings.lunch {
-                        total_cost += day.lunch_day_rate / day.lunch_attendance_count as f64;
+                        let share = a.weight(ServingType::Lunch) / day.lunch_attendance_weight;
+                        day_cost.lunch = day.lunch_day_rate * share;
+                        estimated_cost += day.estimated_rates.lunch * share;
                     }
                     if a.servings.dinner {
-                        total_cost += day.dinner_day_rate / day.dinner_attendance_count as f64;
+                        let share = a.weight(ServingType::Dinner) / day.dinner_attendance_weight;
+                        day_cost.dinner = day.dinner_day_rate * share;
+                        estimated_cost += day.estimated_rates.dinner * share;
                     }
                     if a.servings.snacks {
-                        total_cost += day.snacks_day_rate / day.snacks_attendance_count as f64;
+                        let share = a.weight(ServingType::Snacks) / day.snacks_attendance_weight;
+                        day_cost.snacks = day.snacks_day_rate * share;
+                        estimated_cost += day.estimated_rates.snacks * share;
                     }
                 }
+                total_cost += day_cost.total();
+                p.breakdown.push(day_cost);
             }
             p.cost = total_cost;
+            p.estimated_cost = estimated_cost;
+        }
+
+        for (idx, target_day, servings) in restricted {
+            self.split_among_compatible(idx, target_day, &servings);
         }
+        self.charge_sponsors();
+        self.apply_contributions();
         self.update_costs = false;
     }
 
+    fn split_among_compatible(
+        &mut self,
+        expense_idx: usize,
+        target_day: Option<usize>,
+        servings: &Servings,
+    ) {
+        let e = &self.expenses[expense_idx];
+        let mut costs = ServingCosts::default();
+        costs.add(e.base_price, servings, &e.split);
+        for serving in ServingType::ALL {
+            let amount = costs.get(serving);
+            if amount == 0.0 {
+                continue;
+            }
+            let days: Vec<usize> = match target_day {
+                Some(idx) => vec![idx],
+                None => (0..self.days.len())
+                    .filter(|x| self.days[*x].servings.get(serving))
+                    .collect(),
+            };
+            for day_idx in days.iter() {
+                let day_amount = amount / days.len() as f64;
+                let attending: Vec<usize> = (0..self.people.len())
+                    .filter(|x| self.people[*x].attendance[*day_idx].attends(serving))
+                    .collect();
+                let mut paying: Vec<usize> = attending
+                    .iter()
+                    .copied()
+                    .filter(|x| is_compatible(&self.people[*x].dietary_tags, &e.dietary_tags))
+                    .collect();
+                if paying.is_empty() {
+                    paying = attending;
+                }
+                let day = &mut self.days[*day_idx];
+                if e.estimate {
+                    match serving {
+                        ServingType::Breakfast => day.estimated_rates.breakfast += day_amount,
+                        ServingType::Lunch => day.estimated_rates.lunch += day_amount,
+                        ServingType::Dinner => day.estimated_rates.dinner += day_amount,
+                        ServingType::Snacks => day.estimated_rates.snacks += day_amount,
+                    }
+                    day.estimated_rates.total += day_amount;
+                } else {
+                    match serving {
+                        ServingType::Breakfast => day.breakfast_day_rate += day_amount,
+                        ServingType::Lunch => day.lunch_day_rate += day_amount,
+                        ServingType::Dinner => day.dinner_day_rate += day_amount,
+                        ServingType::Snacks => day.snacks_day_rate += day_amount,
+                    }
+                    day.total_day_rate += day_amount;
+                }
+                let paying_weight: f64 = paying
+                    .iter()
+                    .map(|x| self.people[*x].attendance[*day_idx].weight(serving))
+                    .sum();
+                for person_idx in paying.iter() {
+                    let p = &mut self.people[*person_idx];
+                    let share = day_amount * p.attendance[*day_idx].weight(serving) / paying_weight;
+                    if e.estimate {
+                        p.estimated_cost += share;
+                    } else {
+                        *p.breakdown[*day_idx].get_mut(serving) += share;
+                        p.cost += share;
+                    }
+                }
+            }
+        }
+    }
+
+    fn sponsor_of(&self, person_idx: usize) -> Option<usize> {
+        let sponsor = self.people[person_idx].sponsor.as_ref()?;
+        self.people
+            .iter()
+            .position(|x| &x.name == sponsor && x.sponsor.is_none())
+    }
+
+    fn charge_sponsors(&mut self) {
+        for guest_idx in 0..self.people.len() {
+            self.people[guest_idx].sponsored_cost = 0.0;
+            let Some(sponsor_idx) = self.sponsor_of(guest_idx) else {
+                continue;
+            };
+            let guest = &mut self.people[guest_idx];
+            let day_costs: Vec<f64> = guest.breakdown.iter().map(|x| x.total()).collect();
+            let cost = std::mem::take(&mut guest.cost);
+            let estimated_cost = std::mem::take(&mut guest.estimated_cost);
+            guest.sponsored_cost = cost;
+            let sponsor = &mut self.people[sponsor_idx];
+            for (c, amount) in sponsor.breakdown.iter_mut().zip(day_costs) {
+                c.guests += amount;
+            }
+            sponsor.cost += cost;
+            sponsor.estimated_cost += estimated_cost;
+        }
+    }
+
+    fn apply_contributions(&mut self) {
+        let payers: Vec<usize> = (0..self.people.len())
+            .filter(|x| self.sponsor_of(*x).is_none())
+            .collect();
+        for p in self.people.iter_mut() {
+            p.adjustment = 0.0;
+        }
+        let shares: Vec<(Contribution, f64)> = payers
+            .iter()
+            .map(|x| (self.people[*x].contribution, self.people[*x].cost))
+            .collect();
+        let estimated_shares: Vec<(Contribution, f64)> = payers
+            .iter()
+            .map(|x| (self.people[*x].contribution, self.people[*x].estimated_cost))
+            .collect();
+        let (costs, remainder) = Contribution::settle(&shares);
+        let (estimated_costs, _) = Contribution::settle(&estimated_shares);
+        for (i, idx) in payers.iter().enumerate() {
+            let p = &mut self.people[*idx];
+            p.adjustment = costs[i] - p.cost;
+            p.cost = costs[i];
+            p.estimated_cost = estimated_costs[i];
+        }
+        self.unassigned_contribution = remainder;
+    }
+
     fn update_attendances(&mut self) {
         for (idx, d) in self.days.iter_mut().enumerate() {
             if d.servings.breakfast {
@@ -190,6 +642,11 @@ impl MoekkiCalcApp {
                         a.present && a.servings.breakfast
                     })
                     .count();
+                d.breakfast_attendance_weight = self
+                    .people
+                    .iter()
+                    .map(|x| x.attendance[idx].weight(ServingType::Breakfast))
+                    .sum();
             } else {
                 for p in self.people.iter_mut() {
                     p.attendance.get_mut(idx).unwrap().servings.breakfast = false;
@@ -204,6 +661,11 @@ impl MoekkiCalcApp {
                         a.present && a.servings.lunch
                     })
                     .count();
+                d.lunch_attendance_weight = self
+                    .people
+                    .iter()
+                    .map(|x| x.attendance[idx].weight(ServingType::Lunch))
+                    .sum();
             } else {
                 for p in self.people.iter_mut() {
                     p.attendance.get_mut(idx).unwrap().servings.lunch = false;
@@ -218,6 +680,11 @@ impl MoekkiCalcApp {
                         a.present && a.servings.dinner
                     })
                     .count();
+                d.dinner_attendance_weight = self
+                    .people
+                    .iter()
+                    .map(|x| x.attendance[idx].weight(ServingType::Dinner))
+                    .sum();
             } else {
                 for p in self.people.iter_mut() {
                     p.attendance.get_mut(idx).unwrap().servings.dinner = false;
@@ -232,6 +699,11 @@ impl MoekkiCalcApp {
                         a.present && a.servings.snacks
                     })
                     .count();
+                d.snacks_attendance_weight = self
+                    .people
+                    .iter()
+                    .map(|x| x.attendance[idx].weight(ServingType::Snacks))
+                    .sum();
             } else {
                 for p in self.people.iter_mut() {
                     p.attendance.get_mut(idx).unwrap().servings.snacks = false;
@@ -241,9 +713,118 @@ impl MoekkiCalcApp {
         self.update_attendances = false;
     }
 
+    fn apply_attendance_actions(&mut self) {
+        while let Some(action) = self.attendance_actions.pop() {
+            match action {
+                AttendanceAction::EveryoneAttendsAll => {
+                    for p in self.people.iter_mut() {
+                        p.attend_all(&self.days);
+                    }
+                }
+                AttendanceAction::AttendAll(idx) => {
+                    self.people[idx].attend_all(&self.days);
+                }
+                AttendanceAction::CopyFrom { from, to } => {
+                    let attendance = self.people[from].attendance.clone();
+                    self.people[to].copy_attendance(&attendance);
+                }
+                AttendanceAction::ClearDay(day_idx) => {
+                    for p in self.people.iter_mut() {
+                        p.attendance[day_idx].present = false;
+                    }
+                }
+            }
+            self.update_attendances = true;
+        }
+    }
+
+    fn generate_report(&mut self) {
+        let report = Report {
+            start_date: self.trip_start_date,
+            days: &self.days,
+            people: &self.people,
+            expenses: &self.expenses,
+            total_breakfast_cost: self.total_breakfast_cost,
+            total_lunch_cost: self.total_lunch_cost,
+            total_dinner_cost: self.total_dinner_cost,
+            total_snacks_cost: self.total_snacks_cost,
+            total_cost: self.total_cost,
+            currency: &self.currency,
+            exchange_rates: &self.exchange_rates,
+        };
+        self.report_text = report.generate(self.report_format);
+    }
+
+    fn visible_expenses(&self) -> Vec<usize> {
+        let search = self.expense_search.trim().to_lowercase();
+        let mut order: Vec<usize> = (0..self.expenses.len())
+            .filter(|x| {
+                let e = &self.expenses[*x];
+                e.name.to_lowercase().contains(&search)
+                    && self.expense_category_filter.is_none_or(|x| x == e.category)
+                    && self
+                        .expense_serving_filter
+                        .is_none_or(|x| e.serving_type.get(x))
+                    && self
+                        .expense_payer_filter
+                        .as_ref()
+                        .is_none_or(|x| *x == e.paid_by)
+                    && e.has_tag_matching(&self.expense_tag_filter)
+            })
+            .collect();
+        match self.expense_sort {
+            ExpenseSort::Added => {}
+            ExpenseSort::Date => order.sort_by_key(|x| {
+                let incurred = self.expenses[*x].incurred;
+                (incurred.is_none(), incurred)
+            }),
+            ExpenseSort::Name => order.sort_by_key(|x| self.expenses[*x].name.to_lowercase()),
+            ExpenseSort::Price => order.sort_by(|a, b| {
+                self.expenses[*a]
+                    .base_price
+                    .total_cmp(&self.expenses[*b].base_price)
+            }),
+        }
+        if self.expense_sort_descending {
+            order.reverse();
+        }
+        order
+    }
+
+    fn visible_people(&self) -> Vec<usize> {
+        let search = self.person_search.trim().to_lowercase();
+        let mut order: Vec<usize> = (0..self.people.len())
+            .filter(|x| self.people[*x].name.to_lowercase().contains(&search))
+            .collect();
+        match self.person_sort {
+            PersonSort::Added => {}
+            PersonSort::Name => order.sort_by_key(|x| self.people[*x].name.to_lowercase()),
+            PersonSort::Cost => {
+                order.sort_by(|a, b| self.people[*a].cost.total_cmp(&self.people[*b].cost))
+            }
+        }
+        if self.person_sort_descending {
+            order.reverse();
+        }
+        order
+    }
+
     fn update_removed(&mut self) {
         while let Some(idx) = self.expenses_to_remove.pop() {
-            self.expenses.remove(idx);
+            let e = self.expenses.remove(idx);
+            self.receipt_images.remove(e.id);
+        }
+        while let Some(idx) = self.exchange_rates_to_remove.pop() {
+            self.exchange_rates.remove(idx);
+        }
+        while let Some((day_idx, idx)) = self.menu_items_to_remove.pop() {
+            self.days[day_idx].menu.remove(idx);
+        }
+        while let Some((recipe_idx, idx)) = self.ingredients_to_remove.pop() {
+            self.recipes[recipe_idx].ingredients.remove(idx);
+        }
+        while let Some(idx) = self.recipes_to_remove.pop() {
+            self.recipes.remove(idx);
         }
         while let Some(idx) = self.people_to_remove.pop() {
             self.people.remove(idx);
@@ -257,271 +838,1291 @@ impl MoekkiCalcApp {
         self.update_costs = true;
     }
 
+    fn attach_picked_receipts(&mut self, ctx: &egui::Context) {
+        let picked: Vec<_> = self.picked_receipts.lock().unwrap().drain(..).collect();
+        for file in picked {
+            // The expense may have been removed while the file dialog was open.
+            let Some(e) = self.expenses.iter_mut().find(|x| x.id == file.expense_id) else {
+                continue;
+            };
+            match receipt::encode(&file.bytes) {
+                Ok(data) => {
+                    e.receipt = Some(receipt::Receipt {
+                        file_name: file.file_name,
+                    });
+                    self.receipt_images.insert(file.expense_id, data);
+                    self.receipt_error = None;
+                    if self.receipt_view.as_ref().map(|x| x.0) == Some(file.expense_id) {
+                        self.open_receipt(ctx, file.expense_id);
+                    }
+                }
+                Err(err) => self.receipt_error = Some((file.expense_id, err)),
+            }
+        }
+    }
+
+    fn open_receipt(&mut self, ctx: &egui::Context, expense_id: u64) {
+        match self.receipt_images.image(expense_id) {
+            Some(image) => {
+                let texture = ctx.load_texture(
+                    format!("receipt-{}", expense_id),
+                    image,
+                    egui::TextureOptions::LINEAR,
+                );
+                self.receipt_view = Some((expense_id, texture));
+            }
+            None => {
+                self.receipt_error = Some((
+                    expense_id,
+                    tr("The stored image is missing or damaged").to_string(),
+                ));
+            }
+        }
+    }
+
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.input_mut(|i| i.consume_shortcut(&FOCUS_PERSON_SHORTCUT)) {
+            self.section = Section::People;
+            ctx.memory_mut(|m| m.request_focus(egui::Id::new(NEW_PERSON_FIELD)));
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&FOCUS_EXPENSE_SHORTCUT)) {
+            self.section = Section::Expenses;
+            ctx.memory_mut(|m| m.request_focus(egui::Id::new(NEW_EXPENSE_FIELD)));
+        }
+    }
+
     fn render_top_panel(&mut self, ctx: &egui::Context) {
+        let palette = Palette::new(&ctx.style().visuals);
         egui::TopBottomPanel::top("top-panel")
             .frame(
                 egui::Frame::none()
-                    .stroke(Stroke::new(1.0, Color32::GRAY))
+                    .fill(ctx.style().visuals.panel_fill)
+                    .stroke(palette.frame_stroke)
                     .inner_margin(egui::style::Margin::symmetric(10.0, 10.0)),
             )
             .show(ctx, |ui| {
+                if is_narrow(ctx) {
+                    ui.horizontal(|ui| {
+                        ui.heading("Moekki-Calc");
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::TOP), |ui| {
+                            self.render_language_selector(ui);
+                            self.render_appearance_menu(ui);
+                        });
+                    });
+                    ui.horizontal_wrapped(|ui| {
+                        self.render_menu_buttons(ui);
+                    });
+                    return;
+                }
                 ui.horizontal(|ui| {
                     ui.heading("Moekki-Calc");
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::TOP), |ui| {
-                        if ui.button("Reset session").clicked() {
-                            self.days.clear();
-                            self.people.clear();
-                            self.expenses.clear();
-                        }
+                        self.render_menu_buttons(ui);
+                        ui.add_space(10.0);
+                        self.render_language_selector(ui);
+                        ui.label(tr("Language:"));
+                        self.render_appearance_menu(ui);
                     });
                 });
             });
     }
 
-    fn render_central_panel(&mut self, ctx: &egui::Context) {
-        egui::CentralPanel::default()
-            .frame(
-                egui::Frame::none()
-                    .fill(Color32::from_rgb(10, 25, 30))
-                    .inner_margin(egui::style::Margin::symmetric(80.0, 50.0)),
-            )
-            .show(ctx, |ui| {
-                self.update_removed();
-
-                ui.horizontal(|ui| {
-                    ui.vertical(|ui| {
-                        self.render_days_frame(ui);
-                    });
-                    ui.add_space(20.0);
-                    ui.vertical(|ui| {
-                        self.render_balances_frame(ui);
-                    });
-                });
-                ui.add_space(20.0);
+    fn render_menu_buttons(&mut self, ui: &mut egui::Ui) {
+        if ui.button(tr("Reset session")).clicked() {
+            self.days.clear();
+            self.people.clear();
+            for e in self.expenses.drain(..) {
+                self.receipt_images.remove(e.id);
+            }
+        }
+        if ui.button(tr("Generate report")).clicked() {
+            self.generate_report();
+            self.show_report = true;
+        }
+        if ui.button(tr("Charts")).clicked() {
+            self.show_charts = true;
+        }
+        if ui.button(tr("Ledger")).clicked() {
+            self.show_ledger = true;
+        }
+        if ui.button(tr("Meal plan")).clicked() {
+            self.show_meal_plan = true;
+        }
+        if ui.button(tr("Recipes")).clicked() {
+            self.show_recipes = true;
+        }
+    }
 
+    fn render_appearance_menu(&mut self, ui: &mut egui::Ui) {
+        ui.menu_button(tr("Appearance"), |ui| {
+            ui.label(RichText::new(tr("Theme")).strong());
+            for mode in ThemeMode::ALL {
+                ui.radio_value(&mut self.theme.mode, mode, mode.name());
+            }
+            ui.separator();
+            ui.label(RichText::new(tr("Accent color")).strong());
+            for accent in Accent::ALL {
                 ui.horizontal(|ui| {
-                    ui.vertical(|ui| {
-                        self.render_people_frame(ui);
-                    });
-                    ui.add_space(20.0);
-                    ui.vertical(|ui| {
-                        self.render_expenses_frame(ui);
-                    });
+                    let (rect, _) = ui.allocate_exact_size(Vec2::splat(12.0), egui::Sense::hover());
+                    ui.painter()
+                        .rect_filled(rect, Rounding::same(2.0), accent.color());
+                    ui.radio_value(&mut self.theme.accent, accent, accent.name());
                 });
+            }
+        });
+    }
 
-                if self.update_attendances {
-                    self.update_attendances();
-                }
-                if self.update_costs {
-                    self.update_costs();
+    fn render_language_selector(&mut self, ui: &mut egui::Ui) {
+        egui::ComboBox::from_id_source("language")
+            .selected_text(self.language.native_name())
+            .show_ui(ui, |ui| {
+                for language in Language::ALL {
+                    if ui
+                        .selectable_value(&mut self.language, language, language.native_name())
+                        .changed()
+                    {
+                        set_language(language);
+                    }
                 }
             });
     }
 
-    fn render_days_frame(&mut self, ui: &mut egui::Ui) {
-        egui::Frame::none()
-            .rounding(Rounding::same(20.0))
-            .stroke(Stroke::new(1.0, Color32::GRAY))
-            .inner_margin(egui::style::Margin::symmetric(20.0, 20.0))
-            .show(ui, |ui| {
-                ui.heading("Trip definition");
-                ui.add_space(8.0);
+    fn render_report_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_report;
+        egui::Window::new(tr("Trip report"))
+            .id(egui::Id::new("report-window"))
+            .open(&mut open)
+            .constrain(true)
+            .default_size(window_size(ctx, Vec2::new(600.0, 500.0)))
+            .show(ctx, |ui| {
                 ui.horizontal(|ui| {
-                    if ui.add(egui::Button::new("Add day")).clicked() {
-                        let day_name = format!("{}", self.days.len() + 1);
-                        self.days.push(Day::new(day_name.clone()));
-                        for p in self.people.iter_mut() {
-                            p.attendance.push(Attendance::new(day_name.clone()));
-                        }
+                    let resp1 = ui.radio_value(&mut self.report_format, ReportFormat::Html, "HTML");
+                    let resp2 =
+                        ui.radio_value(&mut self.report_format, ReportFormat::Markdown, "Markdown");
+                    if resp1.changed() || resp2.changed() {
+                        self.generate_report();
                     }
-                    if ui.add(egui::Button::new("Remove day")).clicked() {
-                        self.days_to_remove.push(self.days.len() - 1);
+                    ui.add_space(10.0);
+                    if ui.button(tr("Refresh")).clicked() {
+                        self.generate_report();
                     }
-                });
-                ui.add_space(10.0);
-                ui.horizontal(|ui| {
-                    for d in self.days.iter_mut() {
-                        ui.vertical(|ui| {
-                            ui.label(RichText::new(format!("Day {}", &d.name)).strong());
-                            let resp1 = ui.checkbox(&mut d.servings.breakfast, "Breakfast");
-                            let resp2 = ui.checkbox(&mut d.servings.lunch, "Lunch");
-                            let resp3 = ui.checkbox(&mut d.servings.dinner, "Dinner");
-                            let resp4 = ui.checkbox(&mut d.servings.snacks, "Snacks");
-                            if resp1.changed()
-                                || resp2.changed()
-                                || resp3.changed()
-                                || resp4.changed
-                            {
-                                self.update_attendances = true;
-                            }
-                            ui.add_space(5.0);
-                            if d.servings.breakfast {
-                                ui.label(format!(
-                                    "Breakfast: {}",
-                                    Currency::new_string(
-                                        &d.breakfast_day_rate.to_string(),
-                                        Some(self.currency_opts_eur.clone())
-                                    )
-                                    .unwrap()
-                                    .format()
-                                ));
-                            }
-                            if d.servings.lunch {
-                                ui.label(format!(
-                                    "Lunch: {}",
-                                    Currency::new_string(
-                                        &d.lunch_day_rate.to_string(),
-                                        Some(self.currency_opts_eur.clone())
-                                    )
-                                    .unwrap()
-                                    .format()
-                                ));
-                            }
-                            if d.servings.dinner {
-                                ui.label(format!(
-                                    "Dinner: {}",
-                                    Currency::new_string(
-                                        &d.dinner_day_rate.to_string(),
-                                        Some(self.currency_opts_eur.clone())
-                                    )
-                                    .unwrap()
-                                    .format()
-                                ));
-                            }
-                            if d.servings.snacks {
-                                ui.label(format!(
-                                    "Snacks: {}",
-                                    Currency::new_string(
-                                        &d.snacks_day_rate.to_string(),
-                                        Some(self.currency_opts_eur.clone())
-                                    )
-                                    .unwrap()
-                                    .format()
-                                ));
-                            }
-                            ui.label(format!(
-                                "Total: {}",
-                                Currency::new_string(
-                                    &d.total_day_rate.to_string(),
-                                    Some(self.currency_opts_eur.clone())
-                                )
-                                .unwrap()
-                                .format()
-                            ));
-                        });
-                        ui.add_space(10.0);
+                    if ui.button(tr("Copy to clipboard")).clicked() {
+                        ui.output_mut(|o| o.copied_text = self.report_text.clone());
                     }
                 });
+                ui.add_space(5.0);
+                egui::ScrollArea::vertical()
+                    .id_source("report-scrollarea")
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(&mut self.report_text.as_str())
+                                .code_editor()
+                                .desired_width(f32::INFINITY),
+                        );
+                    });
             });
+        self.show_report = open;
     }
 
-    fn render_balances_frame(&mut self, ui: &mut egui::Ui) {
-        egui::Frame::none()
-            .rounding(Rounding::same(20.0))
-            .stroke(Stroke::new(1.0, Color32::GRAY))
-            .inner_margin(egui::style::Margin::symmetric(20.0, 20.0))
-            .show(ui, |ui| {
-                ui.heading("Balances");
+    fn render_charts_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_charts;
+        egui::Window::new(tr("Charts"))
+            .id(egui::Id::new("charts-window"))
+            .open(&mut open)
+            .constrain(true)
+            .default_size(window_size(ctx, Vec2::new(500.0, 650.0)))
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical()
+                    .id_source("charts-scrollarea")
+                    .show(ui, |ui| {
+                        ui.label(RichText::new(tr("Cost per person")).strong());
+                        charts::person_costs(ui, &self.people, &self.currency);
+                        ui.add_space(10.0);
+                        ui.label(RichText::new(tr("Day rates by serving")).strong());
+                        charts::day_rates(ui, &self.days, &self.currency);
+                        ui.add_space(10.0);
+                        ui.label(RichText::new(tr("Expenses by serving")).strong());
+                        let slices = [
+                            (ServingType::Breakfast, self.total_breakfast_cost),
+                            (ServingType::Lunch, self.total_lunch_cost),
+                            (ServingType::Dinner, self.total_dinner_cost),
+                            (ServingType::Snacks, self.total_snacks_cost),
+                        ]
+                        .map(|(serving, value)| {
+                            (
+                                serving.name().to_string(),
+                                value,
+                                charts::serving_color(serving),
+                            )
+                        });
+                        charts::pie(ui, &slices, &self.currency);
+                    });
+            });
+        self.show_charts = open;
+    }
+
+    fn render_ledger_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_ledger;
+        egui::Window::new(tr("Ledger"))
+            .id(egui::Id::new("ledger-window"))
+            .open(&mut open)
+            .constrain(true)
+            .default_size(window_size(ctx, Vec2::new(650.0, 600.0)))
+            .show(ctx, |ui| {
+                let mut order: Vec<usize> = (0..self.expenses.len()).collect();
+                order.sort_by_key(|x| {
+                    let incurred = self.expenses[*x].incurred;
+                    (incurred.is_none(), incurred)
+                });
+                if self.expenses.iter().any(|x| x.estimate) {
+                    ui.label(
+                        RichText::new(tr("Estimates are not included in the running total")).weak(),
+                    );
+                    ui.add_space(5.0);
+                }
+                egui::ScrollArea::both()
+                    .id_source("ledger-scrollarea")
+                    .show(ui, |ui| {
+                        egui::Grid::new("ledger-grid")
+                            .num_columns(6)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for header in
+                                    ["Date", "Expense", "Category", "Paid by", "Amount", "Total"]
+                                {
+                                    ui.label(RichText::new(tr(header)).strong());
+                                }
+                                ui.end_row();
+                                let credit_color = Palette::new(ui.visuals()).credit;
+                                let mut running_total = 0.0;
+                                for idx in order {
+                                    let e = &self.expenses[idx];
+                                    ui.label(match e.incurred {
+                                        Some(x) => format!(
+                                            "{} {}",
+                                            format_short_date(x.date()),
+                                            x.format("%H:%M")
+                                        ),
+                                        None => tr("No date").to_string(),
+                                    });
+                                    if e.estimate {
+                                        ui.label(RichText::new(&e.name).italics());
+                                    } else {
+                                        ui.label(&e.name);
+                                    }
+                                    ui.label(e.category.name());
+                                    ui.label(&e.paid_by);
+                                    let amount = RichText::new(self.currency.format(e.base_price));
+                                    ui.label(if e.is_credit() {
+                                        amount.color(credit_color)
+                                    } else {
+                                        amount
+                                    });
+                                    if e.estimate {
+                                        ui.label("");
+                                    } else {
+                                        running_total += e.base_price;
+                                        ui.label(self.currency.format(running_total));
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+        self.show_ledger = open;
+    }
+
+    fn current_trip_date(&self) -> NaiveDate {
+        let today = Local::now().date_naive();
+        if self.days.is_empty() {
+            return today;
+        }
+        let last = self.trip_start_date + Duration::days(self.days.len() as i64 - 1);
+        today.clamp(self.trip_start_date, last)
+    }
+
+    fn render_receipt_window(&mut self, ctx: &egui::Context) {
+        let Some((expense_id, texture)) = self.receipt_view.clone() else {
+            return;
+        };
+        let Some(e) = self.expenses.iter_mut().find(|x| x.id == expense_id) else {
+            self.receipt_view = None;
+            return;
+        };
+        let mut open = true;
+        let mut replace = false;
+        egui::Window::new(tr("Receipt"))
+            .id(egui::Id::new("receipt-window"))
+            .open(&mut open)
+            .constrain(true)
+            .default_size(window_size(ctx, Vec2::new(500.0, 650.0)))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&e.name).strong());
+                    if let Some(r) = &e.receipt {
+                        ui.label(RichText::new(&r.file_name).weak());
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::TOP), |ui| {
+                        if ui.button(tr("Remove")).clicked() {
+                            e.receipt = None;
+                            self.receipt_images.remove(expense_id);
+                        }
+                        replace = ui.button(tr("Replace")).clicked();
+                    });
+                });
+                ui.separator();
+                egui::ScrollArea::both()
+                    .id_source("receipt-scrollarea")
+                    .show(ui, |ui| {
+                        let size = texture.size_vec2();
+                        let scale = (ui.available_width() / size.x).min(1.0);
+                        ui.image(&texture, size * scale);
+                    });
+            });
+        if replace {
+            receipt::pick(ctx, expense_id, &self.picked_receipts);
+        }
+        if !open || e.receipt.is_none() {
+            self.receipt_view = None;
+        }
+    }
+
+    fn render_meal_plan_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_meal_plan;
+        egui::Window::new(tr("Meal plan"))
+            .id(egui::Id::new("meal-plan-window"))
+            .open(&mut open)
+            .constrain(true)
+            .default_size(window_size(ctx, Vec2::new(500.0, 600.0)))
+            .show(ctx, |ui| {
+                if self.days.is_empty() {
+                    ui.label(tr("Add days to the trip to plan meals"));
+                    return;
+                }
+                if self.new_menu_item_day >= self.days.len() {
+                    self.new_menu_item_day = 0;
+                }
+                ui.horizontal(|ui| {
+                    day_selector(
+                        ui,
+                        egui::Id::new("new-menu-item-day"),
+                        &mut self.new_menu_item_day,
+                        &self.days,
+                    );
+                    serving_selector(
+                        ui,
+                        egui::Id::new("new-menu-item-serving"),
+                        &mut self.new_menu_item_serving,
+                    );
+                });
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    ui.label(tr("Item:"));
+                    ui.add_sized(
+                        Vec2::new(150.0, 10.0),
+                        egui::TextEdit::singleline(&mut self.new_menu_item_name),
+                    );
+                    ui.label(tr("Per person:"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.new_menu_item_quantity)
+                            .speed(0.01)
+                            .max_decimals(3)
+                            .clamp_range(RangeInclusive::new(0.0, f64::MAX)),
+                    );
+                    ui.label(tr("Unit:"));
+                    ui.add_sized(
+                        Vec2::new(50.0, 10.0),
+                        egui::TextEdit::singleline(&mut self.new_menu_item_unit),
+                    );
+                });
+                ui.add_space(5.0);
+                let allow_add_item =
+                    !self.new_menu_item_name.is_empty() && self.new_menu_item_quantity > 0.0;
+                if ui
+                    .add_enabled(allow_add_item, egui::Button::new(tr("Add menu item")))
+                    .clicked()
+                {
+                    self.days[self.new_menu_item_day].menu.push(MenuItem::new(
+                        self.new_menu_item_name.clone(),
+                        self.new_menu_item_serving,
+                        self.new_menu_item_quantity,
+                        self.new_menu_item_unit.clone(),
+                    ));
+                    self.new_menu_item_name = String::new();
+                    self.new_menu_item_quantity = 0.0;
+                }
+                if !self.recipes.is_empty() {
+                    ui.add_space(5.0);
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Recipe:"));
+                        egui::ComboBox::from_id_source("new-menu-recipe")
+                            .selected_text(&self.new_menu_recipe)
+                            .show_ui(ui, |ui| {
+                                for r in self.recipes.iter() {
+                                    ui.selectable_value(
+                                        &mut self.new_menu_recipe,
+                                        r.name.clone(),
+                                        &r.name,
+                                    );
+                                }
+                            });
+                        let allow_add_recipe =
+                            find_recipe(&self.recipes, &self.new_menu_recipe).is_some();
+                        if ui
+                            .add_enabled(allow_add_recipe, egui::Button::new(tr("Add recipe")))
+                            .on_hover_text(tr(
+                                "Adds the ingredients to the menu and an estimated expense",
+                            ))
+                            .clicked()
+                        {
+                            self.add_recipe_to_menu();
+                        }
+                    });
+                }
+                ui.add_space(10.0);
+                egui::ScrollArea::vertical()
+                    .id_source("meal-plan-scrollarea")
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        for (day_idx, d) in self.days.iter_mut().enumerate() {
+                            if d.menu.is_empty() {
+                                continue;
+                            }
+                            ui.label(RichText::new(day_label(&d.name)).strong());
+                            for serving in ServingType::ALL {
+                                let portions = d.attendance_weight(serving);
+                                let enabled = d.servings.get(serving);
+                                for (idx, m) in d.menu.iter_mut().enumerate() {
+                                    if m.serving != serving {
+                                        continue;
+                                    }
+                                    ui.horizontal(|ui| {
+                                        ui.label(format!(
+                                            "{}: {} {} × {} = {}",
+                                            serving.name(),
+                                            &m.name,
+                                            format_quantity(m.quantity_per_person, &m.unit),
+                                            format_number(portions, 2),
+                                            format_quantity(
+                                                m.quantity_per_person * portions,
+                                                &m.unit
+                                            )
+                                        ));
+                                        ui.menu_button(
+                                            trf("Suitable for ({})", &[&m.dietary_tags.len()]),
+                                            |ui| {
+                                                dietary_tags_editor(
+                                                    ui,
+                                                    egui::Id::new(("menu-diet", day_idx, idx)),
+                                                    &mut m.dietary_tags,
+                                                );
+                                            },
+                                        );
+                                        if !enabled {
+                                            ui.label(
+                                                RichText::new("!")
+                                                    .color(ui.visuals().error_fg_color)
+                                                    .strong(),
+                                            )
+                                            .on_hover_text(tr(
+                                                "Serving is not enabled for this day",
+                                            ));
+                                        }
+                                        if ui.add(egui::Button::new("x")).clicked() {
+                                            self.menu_items_to_remove.push((day_idx, idx));
+                                        }
+                                    });
+                                }
+                            }
+                            ui.add_space(5.0);
+                        }
+                    });
+                ui.add_space(10.0);
+                ui.separator();
+                let items = shopping_list(&self.days);
+                ui.horizontal(|ui| {
+                    ui.heading(tr("Shopping list"));
+                    if ui.button(tr("Copy as text")).clicked() {
+                        let text = shopping_list_text(&items);
+                        ui.output_mut(|o| o.copied_text = text);
+                    }
+                });
+                ui.add_space(5.0);
+                egui::ScrollArea::vertical()
+                    .id_source("shopping-list-scrollarea")
+                    .show(ui, |ui| {
+                        for i in items.iter() {
+                            ui.label(format!(
+                                "{} {}",
+                                i.name,
+                                format_quantity(i.quantity, &i.unit)
+                            ));
+                        }
+                    });
+            });
+        self.show_meal_plan = open;
+    }
+
+    fn add_recipe_to_menu(&mut self) {
+        let id = self.new_expense_id();
+        let recipe = match find_recipe(&self.recipes, &self.new_menu_recipe) {
+            Some(recipe) => recipe,
+            None => return,
+        };
+        let serving = self.new_menu_item_serving;
+        let day = &mut self.days[self.new_menu_item_day];
+        for i in recipe.ingredients.iter() {
+            day.menu.push(MenuItem::new(
+                i.name.clone(),
+                serving,
+                i.amount_per_portion,
+                i.unit.clone(),
+            ));
+        }
+        let mut expense = Expense::new(
+            id,
+            format!(
+                "{} ({} {})",
+                recipe.name,
+                day_label(&day.name),
+                serving.name()
+            ),
+            0.0,
+            String::new(),
+        );
+        expense.estimate = true;
+        expense.specific_day = true;
+        expense.target_day = day.name.clone();
+        expense.recipe = Some(recipe.name.clone());
+        match serving {
+            ServingType::Breakfast => expense.serving_type.breakfast = true,
+            ServingType::Lunch => expense.serving_type.lunch = true,
+            ServingType::Dinner => expense.serving_type.dinner = true,
+            ServingType::Snacks => expense.serving_type.snacks = true,
+        }
+        self.expenses.push(expense);
+        self.update_costs = true;
+    }
+
+    fn render_recipes_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_recipes;
+        egui::Window::new(tr("Recipes"))
+            .id(egui::Id::new("recipes-window"))
+            .open(&mut open)
+            .constrain(true)
+            .default_size(window_size(ctx, Vec2::new(500.0, 500.0)))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Name:"));
+                    ui.add_sized(
+                        Vec2::new(150.0, 10.0),
+                        egui::TextEdit::singleline(&mut self.new_recipe_name),
+                    );
+                    let allow_add_recipe = !self.new_recipe_name.is_empty()
+                        && find_recipe(&self.recipes, &self.new_recipe_name).is_none();
+                    if ui
+                        .add_enabled(allow_add_recipe, egui::Button::new(tr("Add recipe")))
+                        .clicked()
+                    {
+                        self.recipes.push(Recipe::new(self.new_recipe_name.clone()));
+                        self.new_recipe_name = String::new();
+                    }
+                });
+                ui.add_space(10.0);
+                egui::ScrollArea::vertical()
+                    .id_source("recipes-scrollarea")
+                    .show(ui, |ui| {
+                        for (recipe_idx, r) in self.recipes.iter_mut().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.label(RichText::new(&r.name).strong());
+                                ui.label(trf(
+                                    "{} / portion",
+                                    &[&self.currency.format(r.cost_per_portion())],
+                                ));
+                                if ui.add(egui::Button::new("x")).clicked() {
+                                    self.recipes_to_remove.push(recipe_idx);
+                                }
+                            });
+                            egui::Grid::new(("recipe-grid", recipe_idx))
+                                .num_columns(5)
+                                .show(ui, |ui| {
+                                    ui.label(tr("Ingredient"));
+                                    ui.label(tr("Per portion"));
+                                    ui.label(tr("Unit"));
+                                    ui.label(trf("Price / unit ({})", &[&self.currency.symbol]));
+                                    ui.label("");
+                                    ui.end_row();
+                                    for (idx, i) in r.ingredients.iter_mut().enumerate() {
+                                        ui.add_sized(
+                                            Vec2::new(120.0, 10.0),
+                                            egui::TextEdit::singleline(&mut i.name),
+                                        );
+                                        let resp1 = ui.add(
+                                            egui::DragValue::new(&mut i.amount_per_portion)
+                                                .speed(0.01)
+                                                .max_decimals(3)
+                                                .clamp_range(RangeInclusive::new(0.0, f64::MAX)),
+                                        );
+                                        ui.add_sized(
+                                            Vec2::new(50.0, 10.0),
+                                            egui::TextEdit::singleline(&mut i.unit),
+                                        );
+                                        let resp2 = ui.add(
+                                            egui::DragValue::new(&mut i.unit_price)
+                                                .speed(0.1)
+                                                .max_decimals(self.currency.precision as usize)
+                                                .clamp_range(RangeInclusive::new(0.0, f64::MAX)),
+                                        );
+                                        if resp1.changed() || resp2.changed() {
+                                            self.update_costs = true;
+                                        }
+                                        if ui.add(egui::Button::new("x")).clicked() {
+                                            self.ingredients_to_remove.push((recipe_idx, idx));
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                            if ui.button(tr("Add ingredient")).clicked() {
+                                r.ingredients.push(Ingredient::new(String::new()));
+                            }
+                            ui.add_space(15.0);
+                        }
+                    });
+            });
+        self.show_recipes = open;
+    }
+
+    fn render_central_panel(&mut self, ctx: &egui::Context) {
+        let narrow = is_narrow(ctx);
+        let margin = if narrow {
+            egui::style::Margin::symmetric(8.0, 8.0)
+        } else {
+            egui::style::Margin::symmetric(80.0, 50.0)
+        };
+        let palette = Palette::new(&ctx.style().visuals);
+        egui::CentralPanel::default()
+            .frame(
+                egui::Frame::none()
+                    .fill(palette.background)
+                    .inner_margin(margin),
+            )
+            .show(ctx, |ui| {
+                self.apply_attendance_actions();
+                self.update_removed();
+
+                if narrow {
+                    self.render_sections(ui);
+                } else {
+                    ui.horizontal(|ui| {
+                        ui.vertical(|ui| {
+                            self.render_days_frame(ui);
+                        });
+                        ui.add_space(20.0);
+                        ui.vertical(|ui| {
+                            self.render_balances_frame(ui);
+                        });
+                    });
+                    ui.add_space(20.0);
+
+                    ui.horizontal(|ui| {
+                        ui.vertical(|ui| {
+                            self.render_people_frame(ui);
+                        });
+                        ui.add_space(20.0);
+                        ui.vertical(|ui| {
+                            self.render_expenses_frame(ui);
+                        });
+                    });
+                }
+
+                if self.update_attendances {
+                    self.update_attendances();
+                }
+                if self.update_costs {
+                    self.update_costs();
+                }
+            });
+    }
+
+    fn render_sections(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal_wrapped(|ui| {
+            for section in Section::ALL {
+                ui.selectable_value(&mut self.section, section, section.name());
+            }
+        });
+        ui.add_space(8.0);
+        egui::ScrollArea::both()
+            .id_source("section-scrollarea")
+            .show(ui, |ui| match self.section {
+                Section::Trip => self.render_days_frame(ui),
+                Section::Balances => self.render_balances_frame(ui),
+                Section::People => self.render_people_frame(ui),
+                Section::Expenses => self.render_expenses_frame(ui),
+            });
+    }
+
+    fn render_days_frame(&mut self, ui: &mut egui::Ui) {
+        egui::Frame::none()
+            .rounding(Rounding::same(20.0))
+            .stroke(Palette::new(ui.visuals()).frame_stroke)
+            .inner_margin(egui::style::Margin::symmetric(20.0, 20.0))
+            .show(ui, |ui| {
+                ui.heading(tr("Trip definition"));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.label(tr("Start date:"));
+                    ui.add(
+                        egui_extras::DatePickerButton::new(&mut self.trip_start_date)
+                            .id_source("trip-start-date"),
+                    );
+                });
+                ui.add_space(5.0);
+                self.render_currency_settings(ui);
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    if ui.add(egui::Button::new(tr("Add day"))).clicked() {
+                        let day_name = format!("{}", self.days.len() + 1);
+                        self.days.push(Day::new(day_name.clone()));
+                        for p in self.people.iter_mut() {
+                            p.attendance.push(Attendance::new(day_name.clone()));
+                        }
+                    }
+                    if ui.add(egui::Button::new(tr("Remove day"))).clicked() {
+                        self.days_to_remove.push(self.days.len() - 1);
+                    }
+                });
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    for (idx, d) in self.days.iter_mut().enumerate() {
+                        ui.vertical(|ui| {
+                            ui.label(RichText::new(day_label(&d.name)).strong());
+                            let date = self.trip_start_date + Duration::days(idx as i64);
+                            ui.label(format_short_date(date));
+                            let resp1 = ui.checkbox(&mut d.servings.breakfast, tr("Breakfast"));
+                            let resp2 = ui.checkbox(&mut d.servings.lunch, tr("Lunch"));
+                            let resp3 = ui.checkbox(&mut d.servings.dinner, tr("Dinner"));
+                            let resp4 = ui.checkbox(&mut d.servings.snacks, tr("Snacks"));
+                            if resp1.changed()
+                                || resp2.changed()
+                                || resp3.changed()
+                                || resp4.changed
+                            {
+                                self.update_attendances = true;
+                            }
+                            ui.add_space(5.0);
+                            if d.servings.breakfast {
+                                ui.label(format!(
+                                    "{}: {}",
+                                    ServingType::Breakfast.name(),
+                                    self.currency.format(d.breakfast_day_rate)
+                                ));
+                            }
+                            if d.servings.lunch {
+                                ui.label(format!(
+                                    "{}: {}",
+                                    ServingType::Lunch.name(),
+                                    self.currency.format(d.lunch_day_rate)
+                                ));
+                            }
+                            if d.servings.dinner {
+                                ui.label(format!(
+                                    "{}: {}",
+                                    ServingType::Dinner.name(),
+                                    self.currency.format(d.dinner_day_rate)
+                                ));
+                            }
+                            if d.servings.snacks {
+                                ui.label(format!(
+                                    "{}: {}",
+                                    ServingType::Snacks.name(),
+                                    self.currency.format(d.snacks_day_rate)
+                                ));
+                            }
+                            ui.label(format!(
+                                "{}: {}",
+                                tr("Total"),
+                                self.currency.format(d.total_day_rate)
+                            ));
+                        });
+                        ui.add_space(10.0);
+                    }
+                });
+            });
+    }
+
+    fn render_currency_settings(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("Currency:"));
+            egui::ComboBox::from_id_source("currency-preset")
+                .selected_text(format!(
+                    "{} ({})",
+                    self.currency.code,
+                    self.currency.format(1234.5)
+                ))
+                .show_ui(ui, |ui| {
+                    for (label, preset) in CurrencySettings::presets() {
+                        if ui
+                            .selectable_value(&mut self.currency, preset, label)
+                            .changed()
+                        {
+                            self.update_costs = true;
+                        }
+                    }
+                });
+        });
+        egui::CollapsingHeader::new(tr("Currency format"))
+            .id_source("currency-format")
+            .show(ui, |ui| {
+                egui::Grid::new("currency-format-grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr("Code:"));
+                        if ui
+                            .add_sized(
+                                Vec2::new(60.0, 10.0),
+                                egui::TextEdit::singleline(&mut self.currency.code),
+                            )
+                            .changed()
+                        {
+                            self.update_costs = true;
+                        }
+                        ui.end_row();
+                        ui.label(tr("Symbol:"));
+                        ui.add_sized(
+                            Vec2::new(60.0, 10.0),
+                            egui::TextEdit::singleline(&mut self.currency.symbol),
+                        );
+                        ui.end_row();
+                        ui.label(tr("Pattern:"))
+                            .on_hover_text(tr("# is the amount and ! is the symbol"));
+                        ui.add_sized(
+                            Vec2::new(60.0, 10.0),
+                            egui::TextEdit::singleline(&mut self.currency.pattern),
+                        );
+                        ui.end_row();
+                        ui.label(tr("Negative pattern:"))
+                            .on_hover_text(tr("# is the amount and ! is the symbol"));
+                        ui.add_sized(
+                            Vec2::new(60.0, 10.0),
+                            egui::TextEdit::singleline(&mut self.currency.negative_pattern),
+                        );
+                        ui.end_row();
+                        ui.label(tr("Decimal separator:"));
+                        ui.add_sized(
+                            Vec2::new(60.0, 10.0),
+                            egui::TextEdit::singleline(&mut self.currency.decimal),
+                        );
+                        ui.end_row();
+                        ui.label(tr("Thousand separator:"));
+                        ui.add_sized(
+                            Vec2::new(60.0, 10.0),
+                            egui::TextEdit::singleline(&mut self.currency.separator),
+                        );
+                        ui.end_row();
+                        ui.label(tr("Minor unit digits:"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.currency.precision)
+                                .clamp_range(RangeInclusive::new(0, 4)),
+                        );
+                        ui.end_row();
+                    });
+                ui.label(trf(
+                    "Example: {} / {}",
+                    &[
+                        &self.currency.format(1234.5),
+                        &self.currency.format(-1234.5),
+                    ],
+                ));
+            });
+        egui::CollapsingHeader::new(tr("Exchange rates"))
+            .id_source("exchange-rates")
+            .show(ui, |ui| {
+                for (idx, r) in self.exchange_rates.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(format!("1 {}", &r.code)).strong());
+                        ui.label("=");
+                        let resp = ui.add(
+                            egui::DragValue::new(&mut r.rate)
+                                .speed(0.001)
+                                .max_decimals(6)
+                                .clamp_range(RangeInclusive::new(0.0, f64::MAX)),
+                        );
+                        if resp.changed() {
+                            self.update_costs = true;
+                        }
+                        ui.label(&self.currency.code);
+                        if !r.is_set() {
+                            ui.label(
+                                RichText::new("!")
+                                    .color(ui.visuals().error_fg_color)
+                                    .strong(),
+                            )
+                            .on_hover_text(tr("Enter the exchange rate"));
+                        }
+                        ui.label(tr("Symbol:"));
+                        ui.add_sized(
+                            Vec2::new(40.0, 10.0),
+                            egui::TextEdit::singleline(&mut r.symbol),
+                        );
+                        if ui.add(egui::Button::new("x")).clicked() {
+                            self.exchange_rates_to_remove.push(idx);
+                        }
+                    });
+                }
+                ui.horizontal(|ui| {
+                    ui.label(tr("Code:"));
+                    ui.add_sized(
+                        Vec2::new(60.0, 10.0),
+                        egui::TextEdit::singleline(&mut self.new_exchange_rate_code),
+                    );
+                    let code = self.new_exchange_rate_code.trim().to_uppercase();
+                    let allow_add_rate = !code.is_empty()
+                        && !self.currency.is_base(&code)
+                        && find_rate(&self.exchange_rates, &code).is_none();
+                    if ui
+                        .add_enabled(allow_add_rate, egui::Button::new(tr("Add currency")))
+                        .clicked()
+                    {
+                        self.exchange_rates.push(ExchangeRate::new(code));
+                        self.new_exchange_rate_code = String::new();
+                    }
+                });
+            });
+    }
+
+    fn render_balances_frame(&mut self, ui: &mut egui::Ui) {
+        egui::Frame::none()
+            .rounding(Rounding::same(20.0))
+            .stroke(Palette::new(ui.visuals()).frame_stroke)
+            .inner_margin(egui::style::Margin::symmetric(20.0, 20.0))
+            .show(ui, |ui| {
+                ui.heading(tr("Balances"));
                 ui.add_space(8.0);
+                let show_estimates = self.expenses.iter().any(|x| x.estimate);
                 for p in self.people.iter() {
                     ui.label(
-                        RichText::new(format!(
-                            "{}: {}",
-                            p.name,
-                            Currency::new_string(
-                                &p.cost.to_string(),
-                                Some(self.currency_opts_eur.clone())
-                            )
-                            .unwrap()
-                            .format()
-                        ))
-                        .strong(),
+                        RichText::new(format!("{}: {}", p.name, self.currency.format(p.cost)))
+                            .strong(),
                     );
+                    if let Some(sponsor) = p.sponsor.as_ref().filter(|_| p.sponsored_cost != 0.0) {
+                        ui.label(
+                            RichText::new(trf(
+                                "Paid by {}: {}",
+                                &[sponsor, &self.currency.format(p.sponsored_cost)],
+                            ))
+                            .weak(),
+                        );
+                    }
+                    if p.adjustment != 0.0 {
+                        let share = self.currency.format(p.cost - p.adjustment);
+                        let text = match p.contribution {
+                            Contribution::Shared => trf(
+                                "Share {} adjusted by {} for agreed contributions",
+                                &[&share, &self.currency.format(p.adjustment)],
+                            ),
+                            Contribution::Fixed(_) => {
+                                trf("Fixed contribution, share would be {}", &[&share])
+                            }
+                            Contribution::Capped(_) => {
+                                trf("Capped contribution, share would be {}", &[&share])
+                            }
+                        };
+                        ui.label(RichText::new(text).weak());
+                    }
+                    let guests: f64 = p.breakdown.iter().map(|x| x.guests).sum();
+                    if guests != 0.0 {
+                        ui.label(
+                            RichText::new(trf(
+                                "Includes guests: {}",
+                                &[&self.currency.format(guests)],
+                            ))
+                            .weak(),
+                        );
+                    }
+                    if show_estimates {
+                        ui.horizontal(|ui| {
+                            ui.label(trf(
+                                "Estimated: {}",
+                                &[&self.currency.format(p.estimated_cost)],
+                            ));
+                            ui.label(self.variance_text(ui, p.cost - p.estimated_cost))
+                                .on_hover_text(tr("Actual minus estimated cost"));
+                        });
+                    }
                     ui.add_space(5.0);
                 }
                 ui.add_space(10.0);
                 let covered: f64 = self.people.iter().map(|x| x.cost).sum();
                 ui.horizontal(|ui| {
-                    ui.label(format!(
+                    ui.label(trf(
                         "Expenses covered: {} / {}",
-                        Currency::new_string(
-                            &covered.to_string(),
-                            Some(self.currency_opts_eur.clone())
-                        )
-                        .unwrap()
-                        .format(),
-                        Currency::new_string(
-                            &self.total_cost.to_string(),
-                            Some(self.currency_opts_eur.clone())
-                        )
-                        .unwrap()
-                        .format()
+                        &[
+                            &self.currency.format(covered),
+                            &self.currency.format(self.total_cost),
+                        ],
                     ));
                     if covered < self.total_cost {
-                        ui.label(RichText::new("!").color(Color32::RED).strong())
-                            .on_hover_text("All expenses are not covered yet");
+                        ui.label(
+                            RichText::new("!")
+                                .color(ui.visuals().error_fg_color)
+                                .strong(),
+                        )
+                        .on_hover_text(tr("All expenses are not covered yet"));
                     }
                 });
+                if self.unassigned_contribution.abs() >= 0.005 {
+                    let amount = self.currency.format(self.unassigned_contribution.abs());
+                    let text = if self.unassigned_contribution > 0.0 {
+                        trf(
+                            "Nobody can take over {} left by fixed and capped contributions",
+                            &[&amount],
+                        )
+                    } else {
+                        trf("Fixed contributions exceed the shares by {}", &[&amount])
+                    };
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new("!")
+                                .color(ui.visuals().error_fg_color)
+                                .strong(),
+                        );
+                        ui.label(text);
+                    });
+                }
             });
     }
 
     fn render_people_frame(&mut self, ui: &mut egui::Ui) {
         egui::Frame::none()
             .rounding(Rounding::same(20.0))
-            .stroke(Stroke::new(1.0, Color32::GRAY))
+            .stroke(Palette::new(ui.visuals()).frame_stroke)
             .inner_margin(egui::style::Margin::symmetric(20.0, 20.0))
             .show(ui, |ui| {
-                ui.heading("People & Attendance");
+                ui.heading(tr("People & Attendance"));
                 ui.add_space(8.0);
+                let mut submit = false;
                 ui.horizontal(|ui| {
-                    ui.label("Name:");
-                    ui.add_sized(
-                        Vec2::new(150.0, 10.0),
-                        egui::TextEdit::singleline(&mut self.new_person_name),
-                    );
+                    ui.label(tr("Name:"));
+                    let resp = ui
+                        .add_sized(
+                            Vec2::new(150.0, 10.0),
+                            egui::TextEdit::singleline(&mut self.new_person_name)
+                                .id(egui::Id::new(NEW_PERSON_FIELD)),
+                        )
+                        .on_hover_text(ui.ctx().format_shortcut(&FOCUS_PERSON_SHORTCUT));
+                    submit = submitted(ui, &resp);
                 });
                 ui.add_space(5.0);
                 let allow_add_person = !self.new_person_name.is_empty();
                 if ui
-                    .add_enabled(allow_add_person, egui::Button::new("Add person"))
+                    .add_enabled(allow_add_person, egui::Button::new(tr("Add person")))
                     .clicked()
+                    || (submit && allow_add_person)
                 {
                     self.people
                         .push(Person::new(self.new_person_name.clone(), &self.days));
                     self.new_person_name = String::new();
                     self.update_costs = true;
+                    ui.memory_mut(|m| m.request_focus(egui::Id::new(NEW_PERSON_FIELD)));
+                }
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            !self.people.is_empty(),
+                            egui::Button::new(tr("Everyone attends everything")),
+                        )
+                        .clicked()
+                    {
+                        self.attendance_actions
+                            .push(AttendanceAction::EveryoneAttendsAll);
+                    }
+                    if !self.days.is_empty() {
+                        day_selector(
+                            ui,
+                            egui::Id::new("clear-day"),
+                            &mut self.clear_day_idx,
+                            &self.days,
+                        );
+                        if ui
+                            .add_enabled(
+                                self.clear_day_idx < self.days.len(),
+                                egui::Button::new(tr("Clear day")),
+                            )
+                            .on_hover_text(tr("Mark everyone absent for the selected day"))
+                            .clicked()
+                        {
+                            self.attendance_actions
+                                .push(AttendanceAction::ClearDay(self.clear_day_idx));
+                        }
+                    }
+                });
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut self.attendance_table, false, tr("List"));
+                    ui.selectable_value(&mut self.attendance_table, true, tr("Table"));
+                });
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.add_sized(
+                        Vec2::new(120.0, 10.0),
+                        egui::TextEdit::singleline(&mut self.person_search).hint_text(tr("Search")),
+                    );
+                    sort_selector(
+                        ui,
+                        egui::Id::new("person-sort"),
+                        &mut self.person_sort,
+                        &mut self.person_sort_descending,
+                        &PersonSort::ALL,
+                        PersonSort::name,
+                    );
+                });
+                ui.add_space(10.0);
+                let order = self.visible_people();
+                if self.attendance_table {
+                    self.render_attendance_table(ui, &order);
+                    return;
                 }
-                ui.add_space(20.0);
 
+                let names: Vec<String> = self.people.iter().map(|x| x.name.clone()).collect();
+                let hosts: Vec<String> = self
+                    .people
+                    .iter()
+                    .filter(|x| x.sponsor.is_none())
+                    .map(|x| x.name.clone())
+                    .collect();
+                let missing_sponsors: Vec<bool> = (0..self.people.len())
+                    .map(|x| self.people[x].sponsor.is_some() && self.sponsor_of(x).is_none())
+                    .collect();
                 egui::ScrollArea::vertical()
                     .id_source("people-scrollarea")
                     .min_scrolled_height(600.0)
                     .show(ui, |ui| {
-                        for (idx, p) in self.people.iter_mut().enumerate() {
+                        for idx in order {
+                            let p = &mut self.people[idx];
                             ui.horizontal(|ui| {
                                 ui.label(RichText::new(&p.name).strong());
+                                ui.menu_button(trf("Diet ({})", &[&p.dietary_tags.len()]), |ui| {
+                                    if dietary_tags_editor(
+                                        ui,
+                                        egui::Id::new(("person-diet", idx)),
+                                        &mut p.dietary_tags,
+                                    ) {
+                                        self.update_costs = true;
+                                    }
+                                });
+                                ui.menu_button(tr("Bulk"), |ui| {
+                                    attendance_menu(ui, idx, &names, &mut self.attendance_actions);
+                                });
+                                ui.menu_button(tr("Arrival/departure"), |ui| {
+                                    let mut use_stay = p.stay.is_some();
+                                    let mut changed = ui
+                                        .checkbox(&mut use_stay, tr("Fill attendance from stay"))
+                                        .changed();
+                                    if use_stay && p.stay.is_none() {
+                                        p.stay = Some(Stay::new(&self.days));
+                                    } else if !use_stay {
+                                        p.stay = None;
+                                    }
+                                    if let Some(stay) = p.stay.as_mut() {
+                                        changed |= stay_editor(
+                                            ui,
+                                            egui::Id::new(("person-stay", idx)),
+                                            stay,
+                                            &self.days,
+                                        );
+                                    }
+                                    if changed {
+                                        p.apply_stay(&self.days);
+                                        self.update_attendances = true;
+                                    }
+                                });
+                                let contribution_label = match p.contribution {
+                                    Contribution::Shared => tr("Shared cost").to_string(),
+                                    Contribution::Fixed(x) => {
+                                        trf("Fixed {}", &[&self.currency.format(x)])
+                                    }
+                                    Contribution::Capped(x) => {
+                                        trf("Cap {}", &[&self.currency.format(x)])
+                                    }
+                                };
+                                ui.menu_button(contribution_label, |ui| {
+                                    if contribution_editor(ui, &mut p.contribution) {
+                                        self.update_costs = true;
+                                    }
+                                });
+                                let guest_label = match &p.sponsor {
+                                    Some(sponsor) => trf("Guest of {}", &[sponsor]),
+                                    None => tr("Guest").to_string(),
+                                };
+                                ui.menu_button(guest_label, |ui| {
+                                    if sponsor_editor(ui, idx, &mut p.sponsor, &p.name, &hosts) {
+                                        self.update_costs = true;
+                                    }
+                                });
+                                if missing_sponsors[idx] {
+                                    ui.label(
+                                        RichText::new("!")
+                                            .color(ui.visuals().error_fg_color)
+                                            .strong(),
+                                    )
+                                    .on_hover_text(tr(
+                                        "The sponsor is not in the trip, so the guest pays",
+                                    ));
+                                }
+                                let missing = meals_without_compatible_food(
+                                    p,
+                                    &self.days,
+                                    &self.expenses,
+                                    self.trip_start_date,
+                                );
+                                if !missing.is_empty() {
+                                    let meals: Vec<String> = missing
+                                        .iter()
+                                        .map(|(day, serving)| {
+                                            format!("{} {}", day_label(day), serving.name())
+                                        })
+                                        .collect();
+                                    ui.label(
+                                        RichText::new("!")
+                                            .color(ui.visuals().error_fg_color)
+                                            .strong(),
+                                    )
+                                    .on_hover_text(trf(
+                                        "No compatible food: {}",
+                                        &[&meals.join(", ")],
+                                    ));
+                                }
                                 if ui.add(egui::Button::new("x")).clicked() {
                                     self.people_to_remove.push(idx);
                                 }
                             });
+                            if !p.dietary_tags.is_empty() {
+                                let tags: Vec<&str> =
+                                    p.dietary_tags.iter().map(|x| tr(x)).collect();
+                                ui.label(RichText::new(tags.join(", ")).weak());
+                            }
                             ui.horizontal(|ui| {
                                 for d in p.attendance.iter_mut() {
                                     ui.vertical(|ui| {
                                         ui.horizontal(|ui| {
-                                            ui.label(format!("Day {}", &d.day_name));
-                                            let resp = ui.checkbox(&mut d.present, "Present");
+                                            ui.label(day_label(&d.day_name));
+                                            let resp = ui.checkbox(&mut d.present, tr("Present"));
                                             if resp.changed() {
                                                 self.update_attendances = true;
                                             }
                                         });
                                         if d.present {
-                                            let resp1 =
-                                                ui.checkbox(&mut d.servings.breakfast, "Breakfast");
-                                            let resp2 = ui.checkbox(&mut d.servings.lunch, "Lunch");
+                                            let resp1 = ui.checkbox(
+                                                &mut d.servings.breakfast,
+                                                tr("Breakfast"),
+                                            );
+                                            let resp2 =
+                                                ui.checkbox(&mut d.servings.lunch, tr("Lunch"));
                                             let resp3 =
-                                                ui.checkbox(&mut d.servings.dinner, "Dinner");
+                                                ui.checkbox(&mut d.servings.dinner, tr("Dinner"));
                                             let resp4 =
-                                                ui.checkbox(&mut d.servings.snacks, "Snacks");
+                                                ui.checkbox(&mut d.servings.snacks, tr("Snacks"));
                                             if resp1.changed()
                                                 || resp2.changed()
                                                 || resp3.changed()
@@ -529,6 +2130,15 @@ impl MoekkiCalcApp {
                                             {
                                                 self.update_attendances = true;
                                             }
+                                            ui.menu_button(
+                                                trf("Partial ({})", &[&d.fractions.count()]),
+                                                |ui| {
+                                                    if fractions_editor(ui, d) {
+                                                        self.update_attendances = true;
+                                                        self.update_costs = true;
+                                                    }
+                                                },
+                                            );
                                         }
                                     });
                                     ui.add_space(10.0);
@@ -540,101 +2150,619 @@ impl MoekkiCalcApp {
             });
     }
 
+    fn render_attendance_table(&mut self, ui: &mut egui::Ui, order: &[usize]) {
+        if ui.input(|i| !i.pointer.any_down()) {
+            self.attendance_drag_value = None;
+        }
+        let pointer_pos = ui.input(|i| i.pointer.interact_pos());
+        let mut changed = false;
+        let mut toggle_row = None;
+        let mut toggle_column = None;
+        let names: Vec<String> = self.people.iter().map(|x| x.name.clone()).collect();
+        let days = &self.days;
+        let people = &mut self.people;
+        let drag_value = &mut self.attendance_drag_value;
+        let actions = &mut self.attendance_actions;
+
+        egui::ScrollArea::horizontal()
+            .id_source("attendance-table-scrollarea")
+            .show(ui, |ui| {
+                egui_extras::TableBuilder::new(ui)
+                    .striped(true)
+                    .min_scrolled_height(600.0)
+                    .column(egui_extras::Column::auto().at_least(100.0))
+                    .columns(egui_extras::Column::exact(26.0), days.len() * 4)
+                    .header(40.0, |mut header| {
+                        header.col(|ui| {
+                            ui.strong(tr("Person"));
+                        });
+                        for (day_idx, d) in days.iter().enumerate() {
+                            for serving in ServingType::ALL {
+                                header.col(|ui| {
+                                    let text = format!("{}\n{}", d.name, serving.short_name());
+                                    let resp = ui
+                                        .add_enabled(
+                                            d.servings.get(serving),
+                                            egui::Button::new(text).small().frame(false),
+                                        )
+                                        .on_hover_text(trf(
+                                            "Toggle {} {} for everyone",
+                                            &[&day_label(&d.name), &serving.name()],
+                                        ));
+                                    if resp.clicked() {
+                                        toggle_column = Some((day_idx, serving));
+                                    }
+                                });
+                            }
+                        }
+                    })
+                    .body(|mut body| {
+                        for &person_idx in order.iter() {
+                            let p = &mut people[person_idx];
+                            body.row(24.0, |mut row| {
+                                row.col(|ui| {
+                                    let resp = ui
+                                        .add(egui::Button::new(&p.name).frame(false))
+                                        .on_hover_text(tr(
+                                        "Toggle all meals for this person, right-click for more",
+                                    ));
+                                    if resp.clicked() {
+                                        toggle_row = Some(person_idx);
+                                    }
+                                    resp.context_menu(|ui| {
+                                        attendance_menu(ui, person_idx, &names, actions);
+                                    });
+                                });
+                                for (day_idx, d) in days.iter().enumerate() {
+                                    for serving in ServingType::ALL {
+                                        row.col(|ui| {
+                                            if !d.servings.get(serving) {
+                                                ui.weak("–");
+                                                return;
+                                            }
+                                            let a = &mut p.attendance[day_idx];
+                                            let (rect, resp) = ui.allocate_exact_size(
+                                                Vec2::splat(18.0),
+                                                egui::Sense::drag(),
+                                            );
+                                            if resp.drag_started() {
+                                                *drag_value = Some(!a.attends(serving));
+                                            }
+                                            if let (Some(value), Some(pos)) =
+                                                (*drag_value, pointer_pos)
+                                            {
+                                                if rect.contains(pos) && a.attends(serving) != value
+                                                {
+                                                    a.set_attends(serving, value);
+                                                    changed = true;
+                                                }
+                                            }
+                                            let visuals = ui.style().interact(&resp);
+                                            let fill = if a.attends(serving) {
+                                                ui.visuals().selection.bg_fill
+                                            } else {
+                                                visuals.bg_fill
+                                            };
+                                            ui.painter().rect(
+                                                rect,
+                                                Rounding::same(3.0),
+                                                fill,
+                                                visuals.bg_stroke,
+                                            );
+                                        });
+                                    }
+                                }
+                            });
+                        }
+                    });
+            });
+
+        if let Some(person_idx) = toggle_row {
+            let p = &mut self.people[person_idx];
+            let value = !self.days.iter().enumerate().all(|(idx, d)| {
+                ServingType::ALL
+                    .iter()
+                    .all(|s| !d.servings.get(*s) || p.attendance[idx].attends(*s))
+            });
+            for (a, d) in p.attendance.iter_mut().zip(self.days.iter()) {
+                for serving in ServingType::ALL {
+                    if d.servings.get(serving) {
+                        a.set_attends(serving, value);
+                    }
+                }
+            }
+            changed = true;
+        }
+        if let Some((day_idx, serving)) = toggle_column {
+            let value = !self
+                .people
+                .iter()
+                .all(|x| x.attendance[day_idx].attends(serving));
+            for p in self.people.iter_mut() {
+                p.attendance[day_idx].set_attends(serving, value);
+            }
+            changed = true;
+        }
+        if changed {
+            self.update_attendances = true;
+        }
+    }
+
     fn render_expenses_frame(&mut self, ui: &mut egui::Ui) {
         egui::Frame::none()
             .rounding(Rounding::same(20.0))
-            .stroke(Stroke::new(1.0, Color32::GRAY))
+            .stroke(Palette::new(ui.visuals()).frame_stroke)
             .inner_margin(egui::style::Margin::symmetric(20.0, 20.0))
             .show(ui, |ui| {
-                ui.heading("Expenses");
+                ui.heading(tr("Expenses"));
                 ui.add_space(8.0);
+                let mut submit = false;
                 ui.horizontal(|ui| {
-                    ui.label("Name:");
-                    ui.add_sized(
-                        Vec2::new(150.0, 10.0),
-                        egui::TextEdit::singleline(&mut self.new_expense_name),
-                    );
+                    ui.label(tr("Name:"));
+                    let resp = ui
+                        .add_sized(
+                            Vec2::new(150.0, 10.0),
+                            egui::TextEdit::singleline(&mut self.new_expense_name)
+                                .id(egui::Id::new(NEW_EXPENSE_FIELD)),
+                        )
+                        .on_hover_text(ui.ctx().format_shortcut(&FOCUS_EXPENSE_SHORTCUT));
+                    submit |= submitted(ui, &resp);
                 });
                 ui.add_space(5.0);
+                let price =
+                    parse_amount(&self.new_expense_price, &self.currency).filter(|x| *x != 0.0);
                 ui.horizontal(|ui| {
-                    ui.label("Price:");
-                    ui.add(
-                        egui::DragValue::new(&mut self.new_expense_price)
-                            .speed(0.1)
-                            .max_decimals(2)
-                            .clamp_range(RangeInclusive::new(0.0, 1000.0)),
+                    ui.label(tr("Price:"));
+                    let resp = ui
+                        .add_sized(
+                            Vec2::new(80.0, 10.0),
+                            egui::TextEdit::singleline(&mut self.new_expense_price)
+                                .hint_text(format!("0{}00", self.currency.decimal)),
+                        )
+                        .on_hover_text(tr(
+                            "Negative amounts are credits, e.g. refunds or returned deposits",
+                        ));
+                    submit |= submitted(ui, &resp);
+                    egui::ComboBox::from_id_source("new-expense-currency")
+                        .selected_text(if self.currency.is_base(&self.new_expense_currency) {
+                            self.currency.code.clone()
+                        } else {
+                            self.new_expense_currency.clone()
+                        })
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.new_expense_currency,
+                                String::new(),
+                                &self.currency.code,
+                            );
+                            for r in self.exchange_rates.iter() {
+                                ui.selectable_value(
+                                    &mut self.new_expense_currency,
+                                    r.code.clone(),
+                                    &r.code,
+                                );
+                            }
+                        });
+                    if price.is_none() && !self.new_expense_price.trim().is_empty() {
+                        ui.label(
+                            RichText::new("!")
+                                .color(ui.visuals().error_fg_color)
+                                .strong(),
+                        )
+                        .on_hover_text(tr("Not a valid amount"));
+                    }
+                });
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    let servings = &mut self.new_expense_servings;
+                    ui.checkbox(&mut servings.breakfast, ServingType::Breakfast.name());
+                    ui.checkbox(&mut servings.lunch, ServingType::Lunch.name());
+                    ui.checkbox(&mut servings.dinner, ServingType::Dinner.name());
+                    ui.checkbox(&mut servings.snacks, ServingType::Snacks.name());
+                    ui.checkbox(&mut self.new_expense_estimate, tr("Estimate"))
+                        .on_hover_text(tr("Planned cost, replaced later by actual receipts"));
+                    ui.checkbox(&mut self.new_expense_credit, tr("Credit"))
+                        .on_hover_text(tr(
+                            "Refund or returned deposit that reduces the cost of its servings",
+                        ));
+                });
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    ui.label(tr("Category:"));
+                    category_selector(
+                        ui,
+                        egui::Id::new("new-expense-category"),
+                        &mut self.new_expense_category,
                     );
-                    ui.label(self.currency_opts_eur.symbol());
                 });
                 ui.add_space(5.0);
-                let allow_add_expense =
-                    !self.new_expense_name.is_empty() && self.new_expense_price > 0.0;
+                let allow_add_expense = !self.new_expense_name.is_empty() && price.is_some();
                 if ui
-                    .add_enabled(allow_add_expense, egui::Button::new("Add expense"))
+                    .add_enabled(allow_add_expense, egui::Button::new(tr("Add expense")))
                     .clicked()
+                    || (submit && allow_add_expense)
                 {
-                    self.expenses.push(Expense::new(
+                    let mut expense = Expense::new(
+                        self.new_expense_id(),
                         self.new_expense_name.clone(),
-                        self.new_expense_price,
-                    ));
+                        price.unwrap_or_default(),
+                        self.new_expense_currency.clone(),
+                    );
+                    expense.estimate = self.new_expense_estimate;
+                    expense.credit = self.new_expense_credit;
+                    expense.category = self.new_expense_category;
+                    expense.serving_type = self.new_expense_servings.clone();
+                    expense.incurred = Some(self.current_trip_date().and_time(Local::now().time()));
+                    self.expenses.push(expense);
                     self.new_expense_name = String::new();
-                    self.new_expense_price = 0.0;
+                    self.new_expense_price = String::new();
                     self.update_costs = true;
+                    ui.memory_mut(|m| m.request_focus(egui::Id::new(NEW_EXPENSE_FIELD)));
+                }
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.add_sized(
+                        Vec2::new(120.0, 10.0),
+                        egui::TextEdit::singleline(&mut self.expense_search)
+                            .hint_text(tr("Search")),
+                    );
+                    sort_selector(
+                        ui,
+                        egui::Id::new("expense-sort"),
+                        &mut self.expense_sort,
+                        &mut self.expense_sort_descending,
+                        &ExpenseSort::ALL,
+                        ExpenseSort::name,
+                    );
+                });
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(tr("Filter:"));
+                    egui::ComboBox::from_id_source("expense-category-filter")
+                        .selected_text(match self.expense_category_filter {
+                            Some(category) => category.name(),
+                            None => tr("All categories"),
+                        })
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.expense_category_filter,
+                                None,
+                                tr("All categories"),
+                            );
+                            for category in Category::ALL {
+                                ui.selectable_value(
+                                    &mut self.expense_category_filter,
+                                    Some(category),
+                                    category.name(),
+                                );
+                            }
+                        });
+                    egui::ComboBox::from_id_source("expense-serving-filter")
+                        .selected_text(match self.expense_serving_filter {
+                            Some(serving) => serving.name(),
+                            None => tr("All servings"),
+                        })
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.expense_serving_filter,
+                                None,
+                                tr("All servings"),
+                            );
+                            for serving in ServingType::ALL {
+                                ui.selectable_value(
+                                    &mut self.expense_serving_filter,
+                                    Some(serving),
+                                    serving.name(),
+                                );
+                            }
+                        });
+                    egui::ComboBox::from_id_source("expense-payer-filter")
+                        .selected_text(match &self.expense_payer_filter {
+                            Some(name) => payer_label(name),
+                            None => tr("All payers").to_string(),
+                        })
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.expense_payer_filter,
+                                None,
+                                tr("All payers"),
+                            );
+                            ui.selectable_value(
+                                &mut self.expense_payer_filter,
+                                Some(String::new()),
+                                payer_label(""),
+                            );
+                            for p in self.people.iter() {
+                                ui.selectable_value(
+                                    &mut self.expense_payer_filter,
+                                    Some(p.name.clone()),
+                                    &p.name,
+                                );
+                            }
+                        });
+                    ui.add_sized(
+                        Vec2::new(100.0, 10.0),
+                        egui::TextEdit::singleline(&mut self.expense_tag_filter)
+                            .hint_text(tr("Tag")),
+                    );
+                });
+                let receipts_size = self.receipt_images.total_size();
+                if receipts_size > receipt::STORAGE_WARNING_BYTES {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new("!")
+                                .color(ui.visuals().error_fg_color)
+                                .strong(),
+                        );
+                        ui.label(trf(
+                            "Receipts take {} MB, newer ones may not fit in storage",
+                            &[&format_number(receipts_size as f64 / 1_000_000.0, 1)],
+                        ));
+                    });
                 }
                 ui.add_space(10.0);
+                let order = self.visible_expenses();
+                let current_date = self.current_trip_date();
+                let mut open_receipt = None;
                 egui::ScrollArea::vertical()
                     .id_source("expenses-scrollarea")
                     .min_scrolled_height(600.0)
                     .show(ui, |ui| {
-                        for (idx, e) in self.expenses.iter_mut().enumerate() {
+                        let credit_color = Palette::new(ui.visuals()).credit;
+                        for idx in order {
+                            let e = &mut self.expenses[idx];
                             ui.horizontal(|ui| {
-                                ui.label(RichText::new(&e.name).strong());
-                                ui.label(
-                                    Currency::new_string(
-                                        &e.price.to_string(),
-                                        Some(self.currency_opts_eur.clone()),
-                                    )
-                                    .unwrap()
-                                    .format(),
+                                if e.estimate {
+                                    ui.label(RichText::new(&e.name).strong().italics());
+                                } else {
+                                    ui.label(RichText::new(&e.name).strong());
+                                }
+                                let credit = e.is_credit();
+                                let amount = |text: String| {
+                                    if credit {
+                                        RichText::new(text).color(credit_color)
+                                    } else {
+                                        RichText::new(text)
+                                    }
+                                };
+                                if credit {
+                                    ui.label(amount(tr("Credit").to_string()).small());
+                                }
+                                match self.currency.format_foreign(
+                                    e.signed_price(),
+                                    &e.currency,
+                                    &self.exchange_rates,
+                                ) {
+                                    Some(original) => {
+                                        ui.label(amount(original));
+                                        if find_rate(&self.exchange_rates, &e.currency)
+                                            .is_some_and(|x| x.is_set())
+                                        {
+                                            ui.label(amount(format!(
+                                                "({})",
+                                                self.currency.format(e.base_price)
+                                            )));
+                                        } else {
+                                            ui.label(
+                                                RichText::new("!")
+                                                    .color(ui.visuals().error_fg_color)
+                                                    .strong(),
+                                            )
+                                            .on_hover_text(trf(
+                                                "No exchange rate for {}",
+                                                &[&e.currency],
+                                            ));
+                                        }
+                                    }
+                                    None => {
+                                        ui.label(amount(self.currency.format(e.signed_price())));
+                                    }
+                                }
+                                let mut resp10 = category_selector(
+                                    ui,
+                                    egui::Id::new(("expense-category", idx)),
+                                    &mut e.category,
                                 );
+                                ui.menu_button(trf("Tags ({})", &[&e.tags.len()]), |ui| {
+                                    resp10 |= tags_editor(
+                                        ui,
+                                        egui::Id::new(("expense-tags", idx)),
+                                        &mut e.tags,
+                                        &[],
+                                        tr("e.g. breakfast run"),
+                                    );
+                                });
+                                egui::ComboBox::from_id_source(("expense-payer", idx))
+                                    .selected_text(payer_label(&e.paid_by))
+                                    .show_ui(ui, |ui| {
+                                        ui.selectable_value(
+                                            &mut e.paid_by,
+                                            String::new(),
+                                            payer_label(""),
+                                        );
+                                        for p in self.people.iter() {
+                                            ui.selectable_value(
+                                                &mut e.paid_by,
+                                                p.name.clone(),
+                                                &p.name,
+                                            );
+                                        }
+                                    });
+                                if resp10 {
+                                    self.update_costs = true;
+                                }
+                                if e.receipt.is_some() {
+                                    if ui.button(tr("Receipt")).clicked() {
+                                        open_receipt = Some(e.id);
+                                    }
+                                } else if ui
+                                    .button(tr("Attach receipt"))
+                                    .on_hover_text(tr("Attach a photo of the receipt"))
+                                    .clicked()
+                                {
+                                    receipt::pick(ui.ctx(), e.id, &self.picked_receipts);
+                                }
+                                if let Some((_, err)) =
+                                    self.receipt_error.as_ref().filter(|x| x.0 == e.id)
+                                {
+                                    ui.label(
+                                        RichText::new("!")
+                                            .color(ui.visuals().error_fg_color)
+                                            .strong(),
+                                    )
+                                    .on_hover_text(trf("Could not attach receipt: {}", &[err]));
+                                }
                                 if ui.add(egui::Button::new("x")).clicked() {
                                     self.expenses_to_remove.push(idx);
                                 }
                             });
+                            if !e.tags.is_empty() {
+                                ui.label(RichText::new(e.tags.join(", ")).weak());
+                            }
                             ui.horizontal(|ui| {
-                                let resp1 = ui.checkbox(&mut e.serving_type.breakfast, "Breakfast");
-                                let resp2 = ui.checkbox(&mut e.serving_type.lunch, "Lunch");
-                                let resp3 = ui.checkbox(&mut e.serving_type.dinner, "Dinner");
-                                let resp4 = ui.checkbox(&mut e.serving_type.snacks, "Snacks");
-                                // ui.checkbox(&mut e.specific_day, "Only for one day");
-                                // if e.specific_day {
-                                //     egui::ComboBox::from_id_source("target-day")
-                                //         .selected_text(format!("{:?}", e.target_day))
-                                //         .show_ui(ui, |ui| {
-                                //             for d in self.days.iter() {
-                                //                 ui.selectable_value(
-                                //                     &mut e.target_day,
-                                //                     d.name.clone(),
-                                //                     d.name.clone(),
-                                //                 );
-                                //             }
-                                //         });
-                                // }
+                                let resp1 =
+                                    ui.checkbox(&mut e.serving_type.breakfast, tr("Breakfast"));
+                                let resp2 = ui.checkbox(&mut e.serving_type.lunch, tr("Lunch"));
+                                let resp3 = ui.checkbox(&mut e.serving_type.dinner, tr("Dinner"));
+                                let resp4 = ui.checkbox(&mut e.serving_type.snacks, tr("Snacks"));
+                                let resp5 = ui.checkbox(&mut e.estimate, tr("Estimate"));
+                                let resp9 = ui.checkbox(&mut e.credit, tr("Credit"));
+                                if self.uncharged_expenses.contains(&e.id) {
+                                    ui.label(
+                                        RichText::new("!")
+                                            .color(ui.visuals().error_fg_color)
+                                            .strong(),
+                                    )
+                                    .on_hover_text(tr(
+                                        "Nobody eats these servings, so nobody pays",
+                                    ));
+                                }
+                                let mut resp12 = false;
+                                ui.menu_button(split_label(&e.split, &e.serving_type), |ui| {
+                                    resp12 |= split_editor(ui, &mut e.split, &e.serving_type);
+                                });
+                                let mut resp8 = false;
+                                ui.menu_button(
+                                    trf("Suitable for ({})", &[&e.dietary_tags.len()]),
+                                    |ui| {
+                                        resp8 |= dietary_tags_editor(
+                                            ui,
+                                            egui::Id::new(("expense-diet", idx)),
+                                            &mut e.dietary_tags,
+                                        );
+                                        ui.separator();
+                                        resp8 |= ui
+                                            .checkbox(
+                                                &mut e.exclude_incompatible,
+                                                tr("Only compatible people pay"),
+                                            )
+                                            .changed();
+                                    },
+                                );
+                                let mut resp11 = false;
+                                match e.incurred {
+                                    Some(incurred) => {
+                                        let mut date = incurred.date();
+                                        if ui
+                                            .add(
+                                                egui_extras::DatePickerButton::new(&mut date)
+                                                    .id_source(&format!("expense-date-{}", idx)),
+                                            )
+                                            .changed()
+                                        {
+                                            e.incurred = Some(date.and_time(incurred.time()));
+                                            resp11 = true;
+                                        }
+                                        if !self.days.is_empty()
+                                            && e.inferred_day(self.trip_start_date, self.days.len())
+                                                .is_none()
+                                        {
+                                            ui.label(
+                                                RichText::new("!")
+                                                    .color(ui.visuals().error_fg_color)
+                                                    .strong(),
+                                            )
+                                            .on_hover_text(tr("The date is outside the trip"));
+                                        }
+                                    }
+                                    None => {
+                                        if ui.button(tr("Set date")).clicked() {
+                                            e.incurred =
+                                                Some(current_date.and_time(Local::now().time()));
+                                            resp11 = true;
+                                        }
+                                    }
+                                }
+                                let resp6 =
+                                    ui.checkbox(&mut e.specific_day, tr("Only for one day"));
+                                let mut resp7 = false;
+                                if e.specific_day {
+                                    let target =
+                                        e.target_day_index(&self.days, self.trip_start_date);
+                                    let selected = match (e.target_day.is_empty(), target) {
+                                        (false, _) => day_label(&e.target_day),
+                                        (true, Some(day)) => trf(
+                                            "From date ({})",
+                                            &[&day_label(&self.days[day].name)],
+                                        ),
+                                        (true, None) => tr("From date").to_string(),
+                                    };
+                                    egui::ComboBox::from_id_source(("target-day", idx))
+                                        .selected_text(selected)
+                                        .show_ui(ui, |ui| {
+                                            resp7 |= ui
+                                                .selectable_value(
+                                                    &mut e.target_day,
+                                                    String::new(),
+                                                    tr("From date"),
+                                                )
+                                                .changed();
+                                            for d in self.days.iter() {
+                                                resp7 |= ui
+                                                    .selectable_value(
+                                                        &mut e.target_day,
+                                                        d.name.clone(),
+                                                        day_label(&d.name),
+                                                    )
+                                                    .changed();
+                                            }
+                                        });
+                                    if target.is_none() {
+                                        ui.label(
+                                            RichText::new("!")
+                                                .color(ui.visuals().error_fg_color)
+                                                .strong(),
+                                        )
+                                        .on_hover_text(tr(
+                                            "No matching trip day, so it is split over all days",
+                                        ));
+                                    }
+                                }
                                 ui.add_space(10.0);
                                 if !e.serving_type.breakfast
                                     && !e.serving_type.lunch
                                     && !e.serving_type.dinner
                                     && !e.serving_type.snacks
                                 {
-                                    ui.label(RichText::new("!").color(Color32::RED).strong())
-                                        .on_hover_text(
-                                            "Expense must be assigned to at least one serving",
-                                        );
+                                    ui.label(
+                                        RichText::new("!")
+                                            .color(ui.visuals().error_fg_color)
+                                            .strong(),
+                                    )
+                                    .on_hover_text(tr(
+                                        "Expense must be assigned to at least one serving",
+                                    ));
                                 }
                                 if resp1.changed()
                                     || resp2.changed()
                                     || resp3.changed()
                                     || resp4.changed
+                                    || resp5.changed()
+                                    || resp6.changed()
+                                    || resp7
+                                    || resp8
+                                    || resp9.changed()
+                                    || resp11
+                                    || resp12
                                 {
                                     self.update_costs = true;
                                 }
@@ -642,66 +2770,502 @@ impl MoekkiCalcApp {
                             ui.add_space(10.0);
                         }
                     });
+                if let Some(expense_id) = open_receipt {
+                    self.open_receipt(ui.ctx(), expense_id);
+                }
                 ui.add_space(20.0);
                 ui.horizontal(|ui| {
                     ui.label(format!(
-                        "Total: {}",
-                        Currency::new_string(
-                            &self.total_cost.to_string(),
-                            Some(self.currency_opts_eur.clone())
-                        )
-                        .unwrap()
-                        .format()
+                        "{}: {}",
+                        tr("Total"),
+                        self.currency.format(self.total_cost)
                     ));
                     ui.label(format!(
-                        "Breakfast: {}",
-                        Currency::new_string(
-                            &self.total_breakfast_cost.to_string(),
-                            Some(self.currency_opts_eur.clone())
-                        )
-                        .unwrap()
-                        .format()
+                        "{}: {}",
+                        ServingType::Breakfast.name(),
+                        self.currency.format(self.total_breakfast_cost)
                     ));
                     ui.label(format!(
-                        "Lunch: {}",
-                        Currency::new_string(
-                            &self.total_lunch_cost.to_string(),
-                            Some(self.currency_opts_eur.clone())
-                        )
-                        .unwrap()
-                        .format()
+                        "{}: {}",
+                        ServingType::Lunch.name(),
+                        self.currency.format(self.total_lunch_cost)
                     ));
                     ui.label(format!(
-                        "Dinner: {}",
-                        Currency::new_string(
-                            &self.total_dinner_cost.to_string(),
-                            Some(self.currency_opts_eur.clone())
-                        )
-                        .unwrap()
-                        .format()
+                        "{}: {}",
+                        ServingType::Dinner.name(),
+                        self.currency.format(self.total_dinner_cost)
                     ));
                     ui.label(format!(
-                        "Snacks: {}",
-                        Currency::new_string(
-                            &self.total_snacks_cost.to_string(),
-                            Some(self.currency_opts_eur.clone())
-                        )
-                        .unwrap()
-                        .format()
+                        "{}: {}",
+                        ServingType::Snacks.name(),
+                        self.currency.format(self.total_snacks_cost)
                     ));
                 });
+                if !self.expenses.is_empty() {
+                    ui.add_space(20.0);
+                    self.render_category_totals(ui);
+                }
+                if self.expenses.iter().any(|x| x.estimate) {
+                    ui.add_space(20.0);
+                    self.render_budget(ui);
+                }
                 ui.add_space(20.0);
             });
     }
+
+    fn render_category_totals(&mut self, ui: &mut egui::Ui) {
+        ui.label(RichText::new(tr("By category")).strong());
+        ui.add_space(5.0);
+        let show_estimates = self.expenses.iter().any(|x| x.estimate);
+        egui::Grid::new("category-grid")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("");
+                ui.label(tr("Actual"));
+                if show_estimates {
+                    ui.label(tr("Estimated"));
+                    ui.label(tr("Variance"));
+                }
+                ui.end_row();
+                for category in Category::ALL {
+                    let mut actual = 0.0;
+                    let mut estimated = 0.0;
+                    let mut count = 0;
+                    for e in self.expenses.iter().filter(|x| x.category == category) {
+                        if e.estimate {
+                            estimated += e.base_price;
+                        } else {
+                            actual += e.base_price;
+                        }
+                        count += 1;
+                    }
+                    if count == 0 {
+                        continue;
+                    }
+                    ui.label(category.name());
+                    ui.label(self.currency.format(actual));
+                    if show_estimates {
+                        ui.label(self.currency.format(estimated));
+                        ui.label(self.variance_text(ui, actual - estimated));
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn render_budget(&mut self, ui: &mut egui::Ui) {
+        ui.label(RichText::new(tr("Budget")).strong());
+        ui.add_space(5.0);
+        let rows = [
+            (tr("Total"), self.estimated_costs.total, self.total_cost),
+            (
+                ServingType::Breakfast.name(),
+                self.estimated_costs.breakfast,
+                self.total_breakfast_cost,
+            ),
+            (
+                ServingType::Lunch.name(),
+                self.estimated_costs.lunch,
+                self.total_lunch_cost,
+            ),
+            (
+                ServingType::Dinner.name(),
+                self.estimated_costs.dinner,
+                self.total_dinner_cost,
+            ),
+            (
+                ServingType::Snacks.name(),
+                self.estimated_costs.snacks,
+                self.total_snacks_cost,
+            ),
+        ];
+        egui::Grid::new("budget-grid")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("");
+                ui.label(tr("Estimated"));
+                ui.label(tr("Actual"));
+                ui.label(tr("Variance"));
+                ui.end_row();
+                for (label, estimated, actual) in rows {
+                    ui.label(label);
+                    ui.label(self.currency.format(estimated));
+                    ui.label(self.currency.format(actual));
+                    ui.label(self.variance_text(ui, actual - estimated));
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn variance_text(&self, ui: &egui::Ui, variance: f64) -> RichText {
+        let palette = Palette::new(ui.visuals());
+        let text = RichText::new(self.currency.format_signed(variance));
+        if variance > 0.0 {
+            text.color(palette.over_budget)
+        } else {
+            text.color(palette.under_budget)
+        }
+    }
 }
 
 impl eframe::App for MoekkiCalcApp {
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
         eframe::set_value(storage, eframe::APP_KEY, self);
+        self.receipt_images.save(storage);
     }
 
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        self.theme.apply(ctx, frame.info().system_theme);
+        self.handle_shortcuts(ctx);
+        self.attach_picked_receipts(ctx);
         self.render_top_panel(ctx);
         self.render_central_panel(ctx);
+        self.render_report_window(ctx);
+        self.render_charts_window(ctx);
+        self.render_ledger_window(ctx);
+        self.render_receipt_window(ctx);
+        self.render_meal_plan_window(ctx);
+        self.render_recipes_window(ctx);
     }
 }
+
+fn submitted(ui: &egui::Ui, resp: &egui::Response) -> bool {
+    resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter))
+}
+
+fn is_narrow(ctx: &egui::Context) -> bool {
+    ctx.screen_rect().width() < NARROW_SCREEN_WIDTH
+}
+
+fn window_size(ctx: &egui::Context, size: Vec2) -> Vec2 {
+    size.min(ctx.screen_rect().size() - Vec2::splat(20.0))
+}
+
+fn dietary_tags_editor(ui: &mut egui::Ui, id: egui::Id, tags: &mut Vec<String>) -> bool {
+    tags_editor(ui, id, tags, &DIETARY_TAGS, tr("e.g. fish-free"))
+}
+
+fn tags_editor(
+    ui: &mut egui::Ui,
+    id: egui::Id,
+    tags: &mut Vec<String>,
+    presets: &[&str],
+    hint: &str,
+) -> bool {
+    let mut changed = false;
+    for tag in presets.iter().copied() {
+        let mut checked = tags.iter().any(|x| x == tag);
+        if ui.checkbox(&mut checked, tr(tag)).changed() {
+            if checked {
+                tags.push(tag.to_string());
+            } else {
+                tags.retain(|x| x != tag);
+            }
+            changed = true;
+        }
+    }
+    let mut tag_to_remove = None;
+    for (idx, tag) in tags.iter().enumerate() {
+        if presets.contains(&tag.as_str()) {
+            continue;
+        }
+        ui.horizontal(|ui| {
+            ui.label(tag);
+            if ui.small_button("x").clicked() {
+                tag_to_remove = Some(idx);
+            }
+        });
+    }
+    if let Some(idx) = tag_to_remove {
+        tags.remove(idx);
+        changed = true;
+    }
+    let mut new_tag: String = ui.data_mut(|d| d.get_temp(id)).unwrap_or_default();
+    ui.horizontal(|ui| {
+        ui.add_sized(
+            Vec2::new(100.0, 10.0),
+            egui::TextEdit::singleline(&mut new_tag).hint_text(hint),
+        );
+        let tag = new_tag.trim().to_lowercase();
+        let allow_add_tag = !tag.is_empty() && !tags.contains(&tag);
+        if ui
+            .add_enabled(allow_add_tag, egui::Button::new(tr("Add")))
+            .clicked()
+        {
+            tags.push(tag);
+            new_tag.clear();
+            changed = true;
+        }
+    });
+    ui.data_mut(|d| d.insert_temp(id, new_tag));
+    changed
+}
+
+fn stay_editor(ui: &mut egui::Ui, id: egui::Id, stay: &mut Stay, days: &[Day]) -> bool {
+    let mut changed = false;
+    egui::Grid::new(id).num_columns(3).show(ui, |ui| {
+        ui.label(tr("Arrival:"));
+        changed |= day_selector(ui, id.with("arrival-day"), &mut stay.arrival_day, days);
+        changed |= serving_selector(ui, id.with("arrival-serving"), &mut stay.arrival_serving);
+        ui.end_row();
+        ui.label(tr("Departure:"));
+        changed |= day_selector(ui, id.with("departure-day"), &mut stay.departure_day, days);
+        changed |= serving_selector(
+            ui,
+            id.with("departure-serving"),
+            &mut stay.departure_serving,
+        );
+        ui.end_row();
+    });
+    changed
+}
+
+fn day_selector(ui: &mut egui::Ui, id: egui::Id, selected: &mut usize, days: &[Day]) -> bool {
+    let mut changed = false;
+    let selected_text = match days.get(*selected) {
+        Some(d) => day_label(&d.name),
+        None => String::from("-"),
+    };
+    egui::ComboBox::from_id_source(id)
+        .selected_text(selected_text)
+        .show_ui(ui, |ui| {
+            for (idx, d) in days.iter().enumerate() {
+                changed |= ui
+                    .selectable_value(selected, idx, day_label(&d.name))
+                    .changed();
+            }
+        });
+    changed
+}
+
+fn contribution_editor(ui: &mut egui::Ui, contribution: &mut Contribution) -> bool {
+    let mut changed = false;
+    let amount = contribution.amount().unwrap_or_default();
+    changed |= ui
+        .radio_value(contribution, Contribution::Shared, tr("Shared cost"))
+        .on_hover_text(tr("Pays the attendance-based share"))
+        .changed();
+    changed |= ui
+        .radio_value(
+            contribution,
+            Contribution::Fixed(amount),
+            tr("Fixed amount"),
+        )
+        .on_hover_text(tr("Pays an agreed amount, the rest is shared by others"))
+        .changed();
+    changed |= ui
+        .radio_value(
+            contribution,
+            Contribution::Capped(amount),
+            tr("Capped amount"),
+        )
+        .on_hover_text(tr("Pays the share up to an agreed maximum"))
+        .changed();
+    if let Contribution::Fixed(value) | Contribution::Capped(value) = contribution {
+        ui.horizontal(|ui| {
+            ui.label(tr("Amount:"));
+            changed |= ui
+                .add(
+                    egui::DragValue::new(value)
+                        .speed(1.0)
+                        .clamp_range(0.0..=f64::MAX),
+                )
+                .changed();
+        });
+    }
+    changed
+}
+
+fn sponsor_editor(
+    ui: &mut egui::Ui,
+    idx: usize,
+    sponsor: &mut Option<String>,
+    name: &str,
+    hosts: &[String],
+) -> bool {
+    let mut changed = false;
+    let mut guest = sponsor.is_some();
+    if ui
+        .checkbox(&mut guest, tr("Guest who doesn't pay"))
+        .on_hover_text(tr("Attends meals but the share is charged to a sponsor"))
+        .changed()
+    {
+        *sponsor = guest
+            .then(|| hosts.iter().find(|x| *x != name).cloned())
+            .flatten();
+        changed = true;
+    }
+    if let Some(current) = sponsor.as_mut() {
+        ui.horizontal(|ui| {
+            ui.label(tr("Sponsor:"));
+            egui::ComboBox::from_id_source(("person-sponsor", idx))
+                .selected_text(current.as_str())
+                .show_ui(ui, |ui| {
+                    for host in hosts.iter().filter(|x| *x != name) {
+                        changed |= ui.selectable_value(current, host.clone(), host).changed();
+                    }
+                });
+        });
+    }
+    changed
+}
+
+fn split_label(split: &ServingSplit, servings: &Servings) -> String {
+    if split.is_even(servings) {
+        return tr("Split evenly").to_string();
+    }
+    let shares: Vec<String> = ServingType::ALL
+        .into_iter()
+        .filter(|x| servings.get(*x))
+        .map(|x| {
+            format!(
+                "{} {:.0} %",
+                x.short_name(),
+                split.ratio(x, servings) * 100.0
+            )
+        })
+        .collect();
+    trf("Split {}", &[&shares.join(" / ")])
+}
+
+fn split_editor(ui: &mut egui::Ui, split: &mut ServingSplit, servings: &Servings) -> bool {
+    let mut changed = false;
+    let flagged: Vec<ServingType> = ServingType::ALL
+        .into_iter()
+        .filter(|x| servings.get(*x))
+        .collect();
+    if flagged.len() < 2 {
+        ui.label(RichText::new(tr("Assign the expense to several servings to split it")).weak());
+        return false;
+    }
+    ui.label(RichText::new(tr("Relative share of each serving")).weak());
+    egui::Grid::new("split-grid").num_columns(3).show(ui, |ui| {
+        for serving in flagged {
+            ui.label(serving.name());
+            changed |= ui
+                .add(egui::Slider::new(split.get_mut(serving), 0.0..=1.0).show_value(false))
+                .changed();
+            ui.label(format!("{:.0} %", split.ratio(serving, servings) * 100.0));
+            ui.end_row();
+        }
+    });
+    if ui.button(tr("Split evenly")).clicked() {
+        *split = ServingSplit::default();
+        changed = true;
+    }
+    changed
+}
+
+fn fractions_editor(ui: &mut egui::Ui, attendance: &mut Attendance) -> bool {
+    let mut changed = false;
+    ui.label(RichText::new(tr("Share of a full portion")).weak());
+    for serving in ServingType::ALL {
+        if !attendance.attends(serving) {
+            continue;
+        }
+        ui.horizontal(|ui| {
+            let fraction = attendance.fractions.get_mut(serving);
+            let mut partial = fraction.is_some();
+            if ui.checkbox(&mut partial, serving.name()).changed() {
+                *fraction = partial.then_some(0.5);
+                changed = true;
+            }
+            if let Some(value) = fraction.as_mut() {
+                changed |= ui
+                    .add(egui::Slider::new(value, Fractions::MIN..=1.0).fixed_decimals(2))
+                    .changed();
+            }
+        });
+    }
+    changed
+}
+
+fn payer_label(name: &str) -> String {
+    if name.is_empty() {
+        tr("Payer unknown").to_string()
+    } else {
+        trf("Paid by {}", &[&name])
+    }
+}
+
+fn sort_selector<T: PartialEq + Copy>(
+    ui: &mut egui::Ui,
+    id: egui::Id,
+    selected: &mut T,
+    descending: &mut bool,
+    options: &[T],
+    name: fn(&T) -> &'static str,
+) {
+    ui.label(tr("Sort:"));
+    egui::ComboBox::from_id_source(id)
+        .selected_text(name(selected))
+        .show_ui(ui, |ui| {
+            for option in options.iter() {
+                ui.selectable_value(selected, *option, name(option));
+            }
+        });
+    let arrow = if *descending { "⬇" } else { "⬆" };
+    if ui
+        .button(arrow)
+        .on_hover_text(tr("Reverse order"))
+        .clicked()
+    {
+        *descending = !*descending;
+    }
+}
+
+fn category_selector(ui: &mut egui::Ui, id: egui::Id, selected: &mut Category) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_source(id)
+        .selected_text(selected.name())
+        .show_ui(ui, |ui| {
+            for category in Category::ALL {
+                changed |= ui
+                    .selectable_value(selected, category, category.name())
+                    .changed();
+            }
+        });
+    changed
+}
+
+fn serving_selector(ui: &mut egui::Ui, id: egui::Id, selected: &mut ServingType) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_source(id)
+        .selected_text(selected.name())
+        .show_ui(ui, |ui| {
+            for serving in ServingType::ALL {
+                changed |= ui
+                    .selectable_value(selected, serving, serving.name())
+                    .changed();
+            }
+        });
+    changed
+}
+
+fn attendance_menu(
+    ui: &mut egui::Ui,
+    person_idx: usize,
+    names: &[String],
+    actions: &mut Vec<AttendanceAction>,
+) {
+    if ui.button(tr("Present for all days")).clicked() {
+        actions.push(AttendanceAction::AttendAll(person_idx));
+        ui.close_menu();
+    }
+    ui.menu_button(tr("Copy attendance from"), |ui| {
+        for (idx, name) in names.iter().enumerate() {
+            if idx == person_idx {
+                continue;
+            }
+            if ui.button(name).clicked() {
+                actions.push(AttendanceAction::CopyFrom {
+                    from: idx,
+                    to: person_idx,
+                });
+                ui.close_menu();
+            }
+        }
+    });
+}
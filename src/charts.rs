@@ -0,0 +1,160 @@
+use crate::currency::CurrencySettings;
+use crate::i18n::day_label;
+use crate::types::{Day, Person, ServingType};
+use egui::plot::{Bar, BarChart, Legend, Plot};
+use egui::{epaint::Color32, Pos2, Rounding, Sense, Shape, Stroke, Vec2};
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+pub fn serving_color(serving: ServingType) -> Color32 {
+    match serving {
+        ServingType::Breakfast => Color32::from_rgb(230, 180, 60),
+        ServingType::Lunch => Color32::from_rgb(90, 170, 90),
+        ServingType::Dinner => Color32::from_rgb(200, 90, 80),
+        ServingType::Snacks => Color32::from_rgb(90, 140, 210),
+    }
+}
+
+pub fn person_costs(ui: &mut egui::Ui, people: &[Person], currency: &CurrencySettings) {
+    let names: Vec<String> = people.iter().map(|x| x.name.clone()).collect();
+    let bars: Vec<Bar> = people
+        .iter()
+        .enumerate()
+        .map(|(idx, p)| Bar::new(idx as f64, p.cost).name(&p.name))
+        .collect();
+    let formatter_currency = currency.clone();
+    let chart = BarChart::new(bars)
+        .color(ui.visuals().selection.bg_fill)
+        .element_formatter(Box::new(move |bar, _| {
+            format!("{}: {}", bar.name, formatter_currency.format(bar.value))
+        }));
+    Plot::new("person-costs-plot")
+        .height(200.0)
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .include_y(0.0)
+        .x_axis_formatter(move |x, _| axis_label(&names, x))
+        .show(ui, |plot_ui| plot_ui.bar_chart(chart));
+}
+
+pub fn day_rates(ui: &mut egui::Ui, days: &[Day], currency: &CurrencySettings) {
+    let names: Vec<String> = days.iter().map(|x| day_label(&x.name)).collect();
+    let mut charts: Vec<BarChart> = Vec::new();
+    for serving in ServingType::ALL {
+        let bars: Vec<Bar> = days
+            .iter()
+            .enumerate()
+            .map(|(idx, d)| {
+                let rate = match serving {
+                    ServingType::Breakfast => d.breakfast_day_rate,
+                    ServingType::Lunch => d.lunch_day_rate,
+                    ServingType::Dinner => d.dinner_day_rate,
+                    ServingType::Snacks => d.snacks_day_rate,
+                };
+                Bar::new(idx as f64, rate).name(day_label(&d.name))
+            })
+            .collect();
+        let formatter_currency = currency.clone();
+        let mut chart = BarChart::new(bars)
+            .name(serving.name())
+            .color(serving_color(serving))
+            .element_formatter(Box::new(move |bar, _| {
+                format!(
+                    "{} {}: {}",
+                    bar.name,
+                    serving.name(),
+                    formatter_currency.format(bar.value)
+                )
+            }));
+        let below: Vec<&BarChart> = charts.iter().collect();
+        chart = chart.stack_on(&below);
+        charts.push(chart);
+    }
+    Plot::new("day-rates-plot")
+        .height(200.0)
+        .legend(Legend::default())
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .include_y(0.0)
+        .x_axis_formatter(move |x, _| axis_label(&names, x))
+        .show(ui, |plot_ui| {
+            for chart in charts {
+                plot_ui.bar_chart(chart);
+            }
+        });
+}
+
+pub fn pie(ui: &mut egui::Ui, slices: &[(String, f64, Color32)], currency: &CurrencySettings) {
+    let total: f64 = slices.iter().map(|x| x.1.max(0.0)).sum();
+    ui.horizontal(|ui| {
+        let (rect, _) = ui.allocate_exact_size(Vec2::splat(160.0), Sense::hover());
+        let center = rect.center();
+        let radius = rect.width() / 2.0 - 4.0;
+        let painter = ui.painter_at(rect);
+        if total <= 0.0 {
+            painter.circle_stroke(
+                center,
+                radius,
+                Stroke::new(1.0, ui.visuals().weak_text_color()),
+            );
+        } else {
+            let mut start = -FRAC_PI_2;
+            for (_, value, color) in slices.iter() {
+                let angle = (value.max(0.0) / total) as f32 * TAU;
+                paint_sector(&painter, center, radius, start, angle, *color);
+                start += angle;
+            }
+        }
+        ui.vertical(|ui| {
+            for (label, value, color) in slices.iter() {
+                ui.horizontal(|ui| {
+                    let (swatch, _) = ui.allocate_exact_size(Vec2::splat(12.0), Sense::hover());
+                    ui.painter()
+                        .rect_filled(swatch, Rounding::same(2.0), *color);
+                    let share = if total > 0.0 {
+                        value.max(0.0) / total * 100.0
+                    } else {
+                        0.0
+                    };
+                    ui.label(format!(
+                        "{}: {} ({:.0} %)",
+                        label,
+                        currency.format(*value),
+                        share
+                    ));
+                });
+            }
+        });
+    });
+}
+
+fn paint_sector(
+    painter: &egui::Painter,
+    center: Pos2,
+    radius: f32,
+    start: f32,
+    angle: f32,
+    color: Color32,
+) {
+    // Split into pieces of at most a quarter turn so every polygon stays convex.
+    let pieces = (angle / FRAC_PI_2).ceil().max(1.0) as usize;
+    let piece_angle = angle / pieces as f32;
+    for piece in 0..pieces {
+        let piece_start = start + piece as f32 * piece_angle;
+        let steps = 16;
+        let mut points = vec![center];
+        for step in 0..=steps {
+            let a = piece_start + piece_angle * step as f32 / steps as f32;
+            points.push(center + radius * Vec2::angled(a));
+        }
+        painter.add(Shape::convex_polygon(points, color, Stroke::NONE));
+    }
+}
+
+fn axis_label(names: &[String], x: f64) -> String {
+    if x.fract().abs() > f64::EPSILON || x < 0.0 {
+        return String::new();
+    }
+    names.get(x as usize).cloned().unwrap_or_default()
+}
@@ -0,0 +1,301 @@
+use crate::i18n::tr;
+use currency_rs::{Currency, CurrencyOpts};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CurrencySettings {
+    pub code: String,
+    pub symbol: String,
+    pub pattern: String,
+    pub negative_pattern: String,
+    pub decimal: String,
+    pub separator: String,
+    pub precision: u8,
+}
+
+impl Default for CurrencySettings {
+    fn default() -> Self {
+        Self::eur()
+    }
+}
+
+impl CurrencySettings {
+    pub fn eur() -> Self {
+        Self {
+            code: String::from("EUR"),
+            symbol: String::from("€"),
+            pattern: String::from("#!"),
+            negative_pattern: String::from("-#!"),
+            decimal: String::from("."),
+            separator: String::from(","),
+            precision: 2,
+        }
+    }
+
+    pub fn eur_fi() -> Self {
+        Self {
+            code: String::from("EUR"),
+            symbol: String::from("€"),
+            pattern: String::from("# !"),
+            negative_pattern: String::from("-# !"),
+            decimal: String::from(","),
+            separator: String::from(" "),
+            precision: 2,
+        }
+    }
+
+    pub fn sek() -> Self {
+        Self {
+            code: String::from("SEK"),
+            symbol: String::from("kr"),
+            pattern: String::from("# !"),
+            negative_pattern: String::from("-# !"),
+            decimal: String::from(","),
+            separator: String::from(" "),
+            precision: 2,
+        }
+    }
+
+    pub fn nok() -> Self {
+        Self {
+            code: String::from("NOK"),
+            symbol: String::from("kr"),
+            pattern: String::from("! #"),
+            negative_pattern: String::from("! -#"),
+            decimal: String::from(","),
+            separator: String::from(" "),
+            precision: 2,
+        }
+    }
+
+    pub fn usd() -> Self {
+        Self {
+            code: String::from("USD"),
+            symbol: String::from("$"),
+            pattern: String::from("!#"),
+            negative_pattern: String::from("-!#"),
+            decimal: String::from("."),
+            separator: String::from(","),
+            precision: 2,
+        }
+    }
+
+    pub fn presets() -> Vec<(&'static str, Self)> {
+        vec![
+            (tr("Euro (1,234.50€)"), Self::eur()),
+            (tr("Euro, Finnish (1 234,50 €)"), Self::eur_fi()),
+            (tr("Swedish krona (1 234,50 kr)"), Self::sek()),
+            (tr("Norwegian krone (kr 1 234,50)"), Self::nok()),
+            (tr("US dollar ($1,234.50)"), Self::usd()),
+        ]
+    }
+
+    pub fn opts(&self) -> CurrencyOpts {
+        CurrencyOpts::new()
+            .set_symbol(self.symbol.as_str())
+            .set_pattern(self.pattern.as_str())
+            .set_negative_pattern(self.negative_pattern.as_str())
+            .set_decimal(self.decimal.as_str())
+            .set_separator(self.separator.as_str())
+            .set_precision(self.precision as i64)
+    }
+
+    pub fn format(&self, value: f64) -> String {
+        Currency::new_float(value, Some(self.opts())).format()
+    }
+
+    pub fn format_signed(&self, value: f64) -> String {
+        if value > 0.0 {
+            format!("+{}", self.format(value))
+        } else {
+            self.format(value)
+        }
+    }
+
+    pub fn format_with_symbol(&self, value: f64, symbol: &str) -> String {
+        Currency::new_float(value, Some(self.opts().set_symbol(symbol))).format()
+    }
+
+    pub fn is_base(&self, code: &str) -> bool {
+        code.is_empty() || code == self.code
+    }
+
+    pub fn convert(&self, value: f64, code: &str, rates: &[ExchangeRate]) -> Option<f64> {
+        if self.is_base(code) {
+            return Some(value);
+        }
+        find_rate(rates, code)
+            .filter(|x| x.is_set())
+            .map(|x| value * x.rate)
+    }
+
+    pub fn format_foreign(&self, value: f64, code: &str, rates: &[ExchangeRate]) -> Option<String> {
+        if self.is_base(code) {
+            return None;
+        }
+        match find_rate(rates, code) {
+            Some(rate) => Some(self.format_with_symbol(value, &rate.symbol)),
+            None => Some(self.format_with_symbol(value, code)),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExchangeRate {
+    pub code: String,
+    pub symbol: String,
+    pub rate: f64,
+}
+
+impl ExchangeRate {
+    /// Starts without a rate so nothing is converted until the user enters one.
+    pub fn new(code: String) -> Self {
+        Self {
+            symbol: code.clone(),
+            code,
+            rate: 0.0,
+        }
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.rate > 0.0
+    }
+}
+
+/// Parses a typed amount, accepting both "," and "." as the decimal separator.
+///
+/// Whitespace is ignored. When both marks appear, the last one is the decimal point, so
+/// "1 234,50" and "1,234.50" both parse as 1234.5. A single mark followed by exactly three
+/// digits is a thousands separator unless it is the currency's decimal separator, in
+/// which case the amount is ambiguous and rejected. Thousands separators must split the
+/// digits into groups of three, and anything but digits, marks and a sign is rejected.
+pub fn parse_amount(text: &str, currency: &CurrencySettings) -> Option<f64> {
+    let cleaned: String = text.chars().filter(|x| !x.is_whitespace()).collect();
+    let (sign, unsigned) = match cleaned.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", cleaned.strip_prefix('+').unwrap_or(&cleaned)),
+    };
+    if !unsigned
+        .chars()
+        .all(|c| c.is_ascii_digit() || c == ',' || c == '.')
+    {
+        return None;
+    }
+    let marks: Vec<(usize, char)> = unsigned
+        .char_indices()
+        .filter(|(_, c)| *c == ',' || *c == '.')
+        .collect();
+    let decimal = match marks.as_slice() {
+        [] => None,
+        [(idx, mark)] => {
+            let digits = unsigned.len() - idx - 1;
+            if digits != 3 {
+                Some(*idx)
+            } else if currency.decimal.starts_with(*mark) {
+                return None;
+            } else {
+                None
+            }
+        }
+        [.., (idx, mark)] => {
+            let grouping: Vec<char> = marks[..marks.len() - 1].iter().map(|x| x.1).collect();
+            if grouping.iter().all(|x| x != mark) {
+                Some(*idx)
+            } else if grouping.iter().all(|x| x == mark) {
+                None
+            } else {
+                return None;
+            }
+        }
+    };
+    let (integer, fraction) = match decimal {
+        Some(idx) => (&unsigned[..idx], &unsigned[idx..]),
+        None => (unsigned, ""),
+    };
+    let groups: Vec<&str> = integer.split([',', '.']).collect();
+    if groups.len() > 1
+        && (!(1..=3).contains(&groups[0].len()) || groups[1..].iter().any(|x| x.len() != 3))
+    {
+        return None;
+    }
+    let normalized = format!("{}{}{}", sign, groups.concat(), fraction.replace(',', "."));
+    normalized.parse::<f64>().ok().filter(|x| x.is_finite())
+}
+
+pub fn find_rate<'a>(rates: &'a [ExchangeRate], code: &str) -> Option<&'a ExchangeRate> {
+    rates.iter().find(|x| x.code == code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_amount, CurrencySettings};
+
+    #[test]
+    fn parses_either_decimal_mark() {
+        for currency in [CurrencySettings::eur_fi(), CurrencySettings::usd()] {
+            assert_eq!(parse_amount("12,50", &currency), Some(12.5));
+            assert_eq!(parse_amount("12.50", &currency), Some(12.5));
+        }
+    }
+
+    #[test]
+    fn parses_thousands_separators() {
+        for currency in [CurrencySettings::eur_fi(), CurrencySettings::usd()] {
+            assert_eq!(parse_amount("1 234,50", &currency), Some(1234.5));
+            assert_eq!(parse_amount("1,234.50", &currency), Some(1234.5));
+            assert_eq!(parse_amount("1.234.567", &currency), Some(1234567.0));
+        }
+    }
+
+    #[test]
+    fn three_digits_after_a_single_mark() {
+        assert_eq!(
+            parse_amount("1,200", &CurrencySettings::usd()),
+            Some(1200.0)
+        );
+        assert_eq!(parse_amount("1,200", &CurrencySettings::eur_fi()), None);
+        assert_eq!(
+            parse_amount("1.500", &CurrencySettings::eur_fi()),
+            Some(1500.0)
+        );
+        assert_eq!(parse_amount("1.500", &CurrencySettings::usd()), None);
+    }
+
+    #[test]
+    fn rejects_mixed_grouping() {
+        assert_eq!(parse_amount("1,234.5,6", &CurrencySettings::usd()), None);
+        assert_eq!(parse_amount("abc", &CurrencySettings::usd()), None);
+    }
+
+    #[test]
+    fn rejects_misplaced_thousands_separators() {
+        for currency in [CurrencySettings::eur_fi(), CurrencySettings::usd()] {
+            assert_eq!(parse_amount("12.34,5", &currency), None);
+            assert_eq!(parse_amount("1.5.0", &currency), None);
+            assert_eq!(parse_amount("1234,567.5", &currency), None);
+        }
+    }
+
+    #[test]
+    fn rejects_exponents() {
+        for currency in [CurrencySettings::eur_fi(), CurrencySettings::usd()] {
+            assert_eq!(parse_amount("1e5", &currency), None);
+            assert_eq!(parse_amount("1E5", &currency), None);
+            assert_eq!(parse_amount("inf", &currency), None);
+        }
+    }
+
+    #[test]
+    fn parses_signs() {
+        assert_eq!(
+            parse_amount("-12,50", &CurrencySettings::eur_fi()),
+            Some(-12.5)
+        );
+        assert_eq!(
+            parse_amount("-1,234.50", &CurrencySettings::usd()),
+            Some(-1234.5)
+        );
+        assert_eq!(parse_amount("-", &CurrencySettings::usd()), None);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::types::{Day, Expense, Person, ServingType};
+use chrono::NaiveDate;
+
+pub const DIETARY_TAGS: [&str; 5] = [
+    "vegetarian",
+    "vegan",
+    "gluten-free",
+    "lactose-free",
+    "nut-free",
+];
+
+pub fn is_compatible(requirements: &[String], suitable_for: &[String]) -> bool {
+    requirements.iter().all(|x| suitable_for.contains(x))
+}
+
+/// Whether the expense is food served at the meal. Estimates, credits and non-food
+/// categories are left out.
+pub fn expense_applies_to(
+    expense: &Expense,
+    target_day: Option<usize>,
+    day_idx: usize,
+    serving: ServingType,
+) -> bool {
+    !expense.estimate
+        && !expense.is_credit()
+        && expense.category.is_food()
+        && expense.serving_type.get(serving)
+        && target_day.is_none_or(|x| x == day_idx)
+}
+
+/// Meals the person attends where all the food with known dietary tags is unsuitable for
+/// them. Food without tags is unknown and doesn't count either way.
+pub fn meals_without_compatible_food(
+    person: &Person,
+    days: &[Day],
+    expenses: &[Expense],
+    start_date: NaiveDate,
+) -> Vec<(String, ServingType)> {
+    let mut missing = Vec::new();
+    if person.dietary_tags.is_empty() {
+        return missing;
+    }
+    let targets: Vec<Option<usize>> = expenses
+        .iter()
+        .map(|x| x.target_day_index(days, start_date))
+        .collect();
+    for (day_idx, (a, d)) in person.attendance.iter().zip(days.iter()).enumerate() {
+        for serving in ServingType::ALL {
+            if !a.attends(serving) {
+                continue;
+            }
+            let menu_tags = d
+                .menu
+                .iter()
+                .filter(|x| x.serving == serving)
+                .map(|x| &x.dietary_tags);
+            let expense_tags = expenses
+                .iter()
+                .zip(targets.iter())
+                .filter(|(e, target)| expense_applies_to(e, **target, day_idx, serving))
+                .map(|(e, _)| &e.dietary_tags);
+            let mut has_food = false;
+            let mut has_compatible_food = false;
+            for tags in menu_tags.chain(expense_tags).filter(|x| !x.is_empty()) {
+                has_food = true;
+                has_compatible_food |= is_compatible(&person.dietary_tags, tags);
+            }
+            if has_food && !has_compatible_food {
+                missing.push((d.name.clone(), serving));
+            }
+        }
+    }
+    missing
+}
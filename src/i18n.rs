@@ -0,0 +1,673 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Finnish,
+    Swedish,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::Finnish, Language::Swedish];
+
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Finnish => "Suomi",
+            Language::Swedish => "Svenska",
+        }
+    }
+
+    pub fn decimal_separator(&self) -> char {
+        match self {
+            Language::English => '.',
+            Language::Finnish | Language::Swedish => ',',
+        }
+    }
+}
+
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match LANGUAGE.load(Ordering::Relaxed) {
+        1 => Language::Finnish,
+        2 => Language::Swedish,
+        _ => Language::English,
+    }
+}
+
+/// Looks up the translation of an English message, falling back to the message itself.
+pub fn tr(key: &str) -> &str {
+    let translated = match language() {
+        Language::English => None,
+        Language::Finnish => finnish(key),
+        Language::Swedish => swedish(key),
+    };
+    translated.unwrap_or(key)
+}
+
+/// Translates a message and fills its `{}` placeholders in order.
+pub fn trf(key: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::new();
+    let mut args = args.iter();
+    let mut parts = tr(key).split("{}").peekable();
+    while let Some(part) = parts.next() {
+        out.push_str(part);
+        if parts.peek().is_some() {
+            if let Some(arg) = args.next() {
+                out.push_str(&arg.to_string());
+            }
+        }
+    }
+    out
+}
+
+pub fn day_label(name: &str) -> String {
+    trf("Day {}", &[&name])
+}
+
+pub fn format_number(value: f64, max_decimals: i32) -> String {
+    let factor = 10f64.powi(max_decimals);
+    let rounded = (value * factor).round() / factor;
+    format!("{}", rounded).replace('.', &language().decimal_separator().to_string())
+}
+
+pub fn format_short_date(date: NaiveDate) -> String {
+    let weekday = tr(weekday_key(date.weekday()));
+    match language() {
+        Language::English => format!("{} {:02}.{:02}.", weekday, date.day(), date.month()),
+        Language::Finnish => format!("{} {}.{}.", weekday, date.day(), date.month()),
+        Language::Swedish => format!("{} {}/{}", weekday, date.day(), date.month()),
+    }
+}
+
+pub fn format_date(date: NaiveDate) -> String {
+    let weekday = tr(weekday_key(date.weekday()));
+    match language() {
+        Language::English => format!(
+            "{} {:02}.{:02}.{}",
+            weekday,
+            date.day(),
+            date.month(),
+            date.year()
+        ),
+        Language::Finnish => format!(
+            "{} {}.{}.{}",
+            weekday,
+            date.day(),
+            date.month(),
+            date.year()
+        ),
+        Language::Swedish => format!(
+            "{} {}/{} {}",
+            weekday,
+            date.day(),
+            date.month(),
+            date.year()
+        ),
+    }
+}
+
+fn weekday_key(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Mon",
+        Weekday::Tue => "Tue",
+        Weekday::Wed => "Wed",
+        Weekday::Thu => "Thu",
+        Weekday::Fri => "Fri",
+        Weekday::Sat => "Sat",
+        Weekday::Sun => "Sun",
+    }
+}
+
+fn finnish(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "Language:" => "Kieli:",
+        "Appearance" => "Ulkoasu",
+        "Theme" => "Teema",
+        "System" => "Järjestelmä",
+        "Light" => "Vaalea",
+        "Dark" => "Tumma",
+        "High contrast" => "Suuri kontrasti",
+        "Accent color" => "Korostusväri",
+        "Teal" => "Sinivihreä",
+        "Blue" => "Sininen",
+        "Green" => "Vihreä",
+        "Orange" => "Oranssi",
+        "Purple" => "Violetti",
+        "Pink" => "Pinkki",
+        "Reset session" => "Tyhjennä istunto",
+        "Generate report" => "Luo raportti",
+        "Charts" => "Kaaviot",
+        "Meal plan" => "Ateriasuunnitelma",
+        "Recipes" => "Reseptit",
+        "Trip report" => "Matkaraportti",
+        "Refresh" => "Päivitä",
+        "Copy to clipboard" => "Kopioi leikepöydälle",
+        "Cost per person" => "Kulut henkilöittäin",
+        "Day rates by serving" => "Päivähinnat aterioittain",
+        "Expenses by serving" => "Kulut aterioittain",
+        "Add days to the trip to plan meals" => "Lisää matkalle päiviä suunnitellaksesi aterioita",
+        "Item:" => "Tuote:",
+        "Per person:" => "Per henkilö:",
+        "Unit:" => "Yksikkö:",
+        "Add menu item" => "Lisää ruokalistalle",
+        "Recipe:" => "Resepti:",
+        "Add recipe" => "Lisää resepti",
+        "Adds the ingredients to the menu and an estimated expense" => {
+            "Lisää ainekset ruokalistalle ja arvioidun kulun"
+        }
+        "Day {}" => "Päivä {}",
+        "Suitable for ({})" => "Sopii ({})",
+        "Serving is not enabled for this day" => "Ateria ei ole käytössä tänä päivänä",
+        "Shopping list" => "Ostoslista",
+        "Copy as text" => "Kopioi tekstinä",
+        "Name:" => "Nimi:",
+        "{} / portion" => "{} / annos",
+        "Ingredient" => "Ainesosa",
+        "Per portion" => "Per annos",
+        "Unit" => "Yksikkö",
+        "Price / unit ({})" => "Hinta / yksikkö ({})",
+        "Add ingredient" => "Lisää ainesosa",
+        "Trip definition" => "Matkan tiedot",
+        "Trip" => "Matka",
+        "People" => "Osallistujat",
+        "Start date:" => "Alkupäivä:",
+        "Add day" => "Lisää päivä",
+        "Remove day" => "Poista päivä",
+        "Remove" => "Poista",
+        "Breakfast" => "Aamiainen",
+        "Lunch" => "Lounas",
+        "Dinner" => "Päivällinen",
+        "Snacks" => "Välipalat",
+        "B" => "A",
+        "L" => "L",
+        "D" => "P",
+        "S" => "V",
+        "Total" => "Yhteensä",
+        "Currency:" => "Valuutta:",
+        "Currency format" => "Valuutan muoto",
+        "Code:" => "Koodi:",
+        "Symbol:" => "Symboli:",
+        "Pattern:" => "Muoto:",
+        "Negative pattern:" => "Negatiivinen muoto:",
+        "# is the amount and ! is the symbol" => "# on summa ja ! on symboli",
+        "Decimal separator:" => "Desimaalierotin:",
+        "Thousand separator:" => "Tuhaterotin:",
+        "Minor unit digits:" => "Desimaalien määrä:",
+        "Example: {} / {}" => "Esimerkki: {} / {}",
+        "Exchange rates" => "Valuuttakurssit",
+        "Add currency" => "Lisää valuutta",
+        "Balances" => "Saldot",
+        "Estimated: {}" => "Arvio: {}",
+        "Actual minus estimated cost" => "Toteutunut miinus arvioitu kulu",
+        "Expenses covered: {} / {}" => "Katetut kulut: {} / {}",
+        "All expenses are not covered yet" => "Kaikkia kuluja ei ole vielä katettu",
+        "People & Attendance" => "Osallistujat ja läsnäolo",
+        "Add person" => "Lisää henkilö",
+        "Everyone attends everything" => "Kaikki osallistuvat kaikkeen",
+        "Clear day" => "Tyhjennä päivä",
+        "Mark everyone absent for the selected day" => {
+            "Merkitse kaikki poissaoleviksi valittuna päivänä"
+        }
+        "List" => "Lista",
+        "Table" => "Taulukko",
+        "Diet ({})" => "Ruokavalio ({})",
+        "Bulk" => "Joukkotoiminnot",
+        "Arrival/departure" => "Saapuminen/lähtö",
+        "Fill attendance from stay" => "Täytä läsnäolo oleskelun mukaan",
+        "No compatible food: {}" => "Ei sopivaa ruokaa: {}",
+        "Present" => "Paikalla",
+        "Person" => "Henkilö",
+        "Toggle {} {} for everyone" => "Vaihda {} {} kaikille",
+        "Toggle all meals for this person, right-click for more" => {
+            "Vaihda kaikki henkilön ateriat, lisää toimintoja oikealla painikkeella"
+        }
+        "Expenses" => "Kulut",
+        "Price:" => "Hinta:",
+        "Not a valid amount" => "Virheellinen summa",
+        "Negative amounts are credits, e.g. refunds or returned deposits" => {
+            "Negatiiviset summat ovat hyvityksiä, esim. palautuksia tai palautettuja takuumaksuja"
+        }
+        "Add expense" => "Lisää kulu",
+        "Estimate" => "Arvio",
+        "Credit" => "Hyvitys",
+        "Ledger" => "Kirjanpito",
+        "Date" => "Päivämäärä",
+        "Expense" => "Kulu",
+        "Category" => "Luokka",
+        "Paid by" => "Maksaja",
+        "Amount" => "Summa",
+        "No date" => "Ei päivämäärää",
+        "Set date" => "Aseta päivämäärä",
+        "Estimates are not included in the running total" => {
+            "Arviot eivät sisälly juoksevaan summaan"
+        }
+        "Partial ({})" => "Osittain ({})",
+        "Share of a full portion" => "Osuus täydestä annoksesta",
+        "Guest" => "Vieras",
+        "Guest of {}" => "Vieraana: {}",
+        "Guest who doesn't pay" => "Vieras, joka ei maksa",
+        "Attends meals but the share is charged to a sponsor" => {
+            "Osallistuu aterioille, mutta osuus veloitetaan isännältä"
+        }
+        "Sponsor:" => "Isäntä:",
+        "Paid by {}: {}" => "Maksaja {}: {}",
+        "Includes guests: {}" => "Sisältää vieraat: {}",
+        "The sponsor is not in the trip, so the guest pays" => {
+            "Isäntä ei ole mukana matkalla, joten vieras maksaa itse"
+        }
+        "Shared cost" => "Jaettu kulu",
+        "Fixed {}" => "Kiinteä {}",
+        "Cap {}" => "Enintään {}",
+        "Fixed amount" => "Kiinteä summa",
+        "Capped amount" => "Enimmäissumma",
+        "Amount:" => "Summa:",
+        "Pays the attendance-based share" => "Maksaa osallistumisen mukaisen osuuden",
+        "Pays an agreed amount, the rest is shared by others" => {
+            "Maksaa sovitun summan, loput jaetaan muiden kesken"
+        }
+        "Pays the share up to an agreed maximum" => {
+            "Maksaa osuutensa sovittuun enimmäissummaan asti"
+        }
+        "Share {} adjusted by {} for agreed contributions" => {
+            "Osuutta {} on tasattu {} sovittujen maksujen vuoksi"
+        }
+        "Fixed contribution, share would be {}" => "Kiinteä maksu, osuus olisi {}",
+        "Capped contribution, share would be {}" => "Rajattu maksu, osuus olisi {}",
+        "Split evenly" => "Jaa tasan",
+        "Split {}" => "Jako {}",
+        "Relative share of each serving" => "Aterioiden suhteelliset osuudet",
+        "Assign the expense to several servings to split it" => {
+            "Kohdista kulu useammalle aterialle jakaaksesi sen"
+        }
+        "Nobody can take over {} left by fixed and capped contributions" => {
+            "Kukaan ei voi ottaa maksettavakseen kiinteiden ja rajattujen maksujen jättämää {}"
+        }
+        "Fixed contributions exceed the shares by {}" => {
+            "Kiinteät maksut ylittävät osuudet {} verran"
+        }
+        "The date is outside the trip" => "Päivämäärä on matkan ulkopuolella",
+        "From date" => "Päivämäärän mukaan",
+        "From date ({})" => "Päivämäärän mukaan ({})",
+        "No matching trip day, so it is split over all days" => {
+            "Mikään matkan päivä ei täsmää, joten kulu jaetaan kaikille päiville"
+        }
+        "Nobody eats these servings, so nobody pays" => {
+            "Kukaan ei syö näitä aterioita, joten kukaan ei maksa"
+        }
+        "Moekki-Calc trip report" => "Moekki-Calc matkaraportti",
+        "Trip dates:" => "Matkan päivät:",
+        "Attendance" => "Osallistuminen",
+        "Servings" => "Ateriat",
+        "Day rates" => "Päivähinnat",
+        "Day" => "Päivä",
+        "Euro, Finnish (1 234,50 €)" => "Euro, suomalainen (1 234,50 €)",
+        "Swedish krona (1 234,50 kr)" => "Ruotsin kruunu (1 234,50 kr)",
+        "Norwegian krone (kr 1 234,50)" => "Norjan kruunu (kr 1 234,50)",
+        "US dollar ($1,234.50)" => "Yhdysvaltain dollari ($1,234.50)",
+        "Guest, paid by {}: {}" => "Vieras, maksaja {}: {}",
+        "Guest of {}, who is not in the trip, so they pay" => {
+            "Vieras, isäntä {} ei ole mukana matkalla, joten vieras maksaa itse"
+        }
+        "guests {}" => "vieraat {}",
+        "No days defined" => "Ei päiviä",
+        "B = breakfast, L = lunch, D = dinner, S = snacks" => {
+            "A = aamiainen, L = lounas, P = päivällinen, V = välipalat"
+        }
+        "{} (breakfast {}, lunch {}, dinner {}, snacks {}{})" => {
+            "{} (aamiainen {}, lounas {}, päivällinen {}, välipalat {}{})"
+        }
+        "credit" => "hyvitys",
+        "estimate" => "arvio",
+        "Receipt" => "Kuitti",
+        "Attach receipt" => "Liitä kuitti",
+        "Attach a photo of the receipt" => "Liitä kuva kuitista",
+        "Could not attach receipt: {}" => "Kuitin liittäminen epäonnistui: {}",
+        "The stored image is missing or damaged" => "Tallennettu kuva puuttuu tai on vioittunut",
+        "Receipts take {} MB, newer ones may not fit in storage" => {
+            "Kuitit vievät {} Mt, uudet eivät ehkä mahdu tallennustilaan"
+        }
+        "Images" => "Kuvat",
+        "Replace" => "Vaihda",
+        "Search" => "Haku",
+        "Sort:" => "Järjestys:",
+        "Reverse order" => "Käänteinen järjestys",
+        "Date added" => "Lisäysjärjestys",
+        "Name" => "Nimi",
+        "Price" => "Hinta",
+        "Cost" => "Kulut",
+        "All servings" => "Kaikki ateriat",
+        "All payers" => "Kaikki maksajat",
+        "Payer unknown" => "Maksaja tuntematon",
+        "Paid by {}" => "Maksaja: {}",
+        "Groceries" => "Ruokaostokset",
+        "Alcohol" => "Alkoholi",
+        "Fuel" => "Polttoaine",
+        "Rent" => "Vuokra",
+        "Equipment" => "Varusteet",
+        "Other" => "Muut",
+        "Category:" => "Kategoria:",
+        "Filter:" => "Suodata:",
+        "All categories" => "Kaikki kategoriat",
+        "Tag" => "Tunniste",
+        "Tags ({})" => "Tunnisteet ({})",
+        "e.g. breakfast run" => "esim. aamiaisostokset",
+        "By category" => "Kategorioittain",
+        "Refund or returned deposit that reduces the cost of its servings" => {
+            "Palautus tai pantti, joka pienentää aterioidensa kuluja"
+        }
+        "Planned cost, replaced later by actual receipts" => {
+            "Suunniteltu kulu, korvataan myöhemmin toteutuneilla kuiteilla"
+        }
+        "No exchange rate for {}" => "Valuutalle {} ei ole kurssia",
+        "Enter the exchange rate" => "Syötä valuuttakurssi",
+        "Only compatible people pay" => "Vain sopivat henkilöt maksavat",
+        "Only for one day" => "Vain yhdelle päivälle",
+        "Expense must be assigned to at least one serving" => {
+            "Kulu on kohdistettava vähintään yhteen ateriaan"
+        }
+        "Budget" => "Budjetti",
+        "Estimated" => "Arvioitu",
+        "Actual" => "Toteutunut",
+        "Variance" => "Erotus",
+        "e.g. fish-free" => "esim. kalaton",
+        "Add" => "Lisää",
+        "Arrival:" => "Saapuminen:",
+        "Departure:" => "Lähtö:",
+        "Present for all days" => "Paikalla kaikkina päivinä",
+        "Copy attendance from" => "Kopioi läsnäolo henkilöltä",
+        "vegetarian" => "kasvis",
+        "vegan" => "vegaaninen",
+        "gluten-free" => "gluteeniton",
+        "lactose-free" => "laktoositon",
+        "nut-free" => "pähkinätön",
+        "Mon" => "ma",
+        "Tue" => "ti",
+        "Wed" => "ke",
+        "Thu" => "to",
+        "Fri" => "pe",
+        "Sat" => "la",
+        "Sun" => "su",
+        _ => return None,
+    })
+}
+
+fn swedish(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "Language:" => "Språk:",
+        "Appearance" => "Utseende",
+        "Theme" => "Tema",
+        "System" => "System",
+        "Light" => "Ljust",
+        "Dark" => "Mörkt",
+        "High contrast" => "Hög kontrast",
+        "Accent color" => "Accentfärg",
+        "Teal" => "Blågrön",
+        "Blue" => "Blå",
+        "Green" => "Grön",
+        "Orange" => "Orange",
+        "Purple" => "Lila",
+        "Pink" => "Rosa",
+        "Reset session" => "Återställ session",
+        "Generate report" => "Skapa rapport",
+        "Charts" => "Diagram",
+        "Meal plan" => "Måltidsplan",
+        "Recipes" => "Recept",
+        "Trip report" => "Reserapport",
+        "Refresh" => "Uppdatera",
+        "Copy to clipboard" => "Kopiera till urklipp",
+        "Cost per person" => "Kostnad per person",
+        "Day rates by serving" => "Dagspriser per måltid",
+        "Expenses by serving" => "Utgifter per måltid",
+        "Add days to the trip to plan meals" => "Lägg till dagar i resan för att planera måltider",
+        "Item:" => "Vara:",
+        "Per person:" => "Per person:",
+        "Unit:" => "Enhet:",
+        "Add menu item" => "Lägg till på menyn",
+        "Recipe:" => "Recept:",
+        "Add recipe" => "Lägg till recept",
+        "Adds the ingredients to the menu and an estimated expense" => {
+            "Lägger till ingredienserna på menyn och en uppskattad utgift"
+        }
+        "Day {}" => "Dag {}",
+        "Suitable for ({})" => "Passar för ({})",
+        "Serving is not enabled for this day" => "Måltiden är inte aktiverad den här dagen",
+        "Shopping list" => "Inköpslista",
+        "Copy as text" => "Kopiera som text",
+        "Name:" => "Namn:",
+        "{} / portion" => "{} / portion",
+        "Ingredient" => "Ingrediens",
+        "Per portion" => "Per portion",
+        "Unit" => "Enhet",
+        "Price / unit ({})" => "Pris / enhet ({})",
+        "Add ingredient" => "Lägg till ingrediens",
+        "Trip definition" => "Resans uppgifter",
+        "Trip" => "Resa",
+        "People" => "Deltagare",
+        "Start date:" => "Startdatum:",
+        "Add day" => "Lägg till dag",
+        "Remove day" => "Ta bort dag",
+        "Remove" => "Ta bort",
+        "Breakfast" => "Frukost",
+        "Lunch" => "Lunch",
+        "Dinner" => "Middag",
+        "Snacks" => "Mellanmål",
+        "B" => "Fr",
+        "L" => "Lu",
+        "D" => "Mi",
+        "S" => "Me",
+        "Total" => "Totalt",
+        "Currency:" => "Valuta:",
+        "Currency format" => "Valutaformat",
+        "Code:" => "Kod:",
+        "Symbol:" => "Symbol:",
+        "Pattern:" => "Mönster:",
+        "Negative pattern:" => "Negativt mönster:",
+        "# is the amount and ! is the symbol" => "# är beloppet och ! är symbolen",
+        "Decimal separator:" => "Decimaltecken:",
+        "Thousand separator:" => "Tusentalsavgränsare:",
+        "Minor unit digits:" => "Antal decimaler:",
+        "Example: {} / {}" => "Exempel: {} / {}",
+        "Exchange rates" => "Växelkurser",
+        "Add currency" => "Lägg till valuta",
+        "Balances" => "Saldon",
+        "Estimated: {}" => "Uppskattat: {}",
+        "Actual minus estimated cost" => "Faktisk minus uppskattad kostnad",
+        "Expenses covered: {} / {}" => "Täckta utgifter: {} / {}",
+        "All expenses are not covered yet" => "Alla utgifter är inte täckta än",
+        "People & Attendance" => "Deltagare och närvaro",
+        "Add person" => "Lägg till person",
+        "Everyone attends everything" => "Alla deltar i allt",
+        "Clear day" => "Töm dag",
+        "Mark everyone absent for the selected day" => {
+            "Markera alla som frånvarande den valda dagen"
+        }
+        "List" => "Lista",
+        "Table" => "Tabell",
+        "Diet ({})" => "Kost ({})",
+        "Bulk" => "Massåtgärder",
+        "Arrival/departure" => "Ankomst/avresa",
+        "Fill attendance from stay" => "Fyll i närvaro från vistelsen",
+        "No compatible food: {}" => "Ingen lämplig mat: {}",
+        "Present" => "Närvarande",
+        "Person" => "Person",
+        "Toggle {} {} for everyone" => "Växla {} {} för alla",
+        "Toggle all meals for this person, right-click for more" => {
+            "Växla alla måltider för personen, högerklicka för mer"
+        }
+        "Expenses" => "Utgifter",
+        "Price:" => "Pris:",
+        "Not a valid amount" => "Ogiltigt belopp",
+        "Negative amounts are credits, e.g. refunds or returned deposits" => {
+            "Negativa belopp är krediteringar, t.ex. återbetalningar eller återlämnade depositioner"
+        }
+        "Add expense" => "Lägg till utgift",
+        "Estimate" => "Uppskattning",
+        "Credit" => "Kreditering",
+        "Ledger" => "Huvudbok",
+        "Date" => "Datum",
+        "Expense" => "Utgift",
+        "Category" => "Kategori",
+        "Paid by" => "Betalt av",
+        "Amount" => "Belopp",
+        "No date" => "Inget datum",
+        "Set date" => "Ange datum",
+        "Estimates are not included in the running total" => {
+            "Uppskattningar ingår inte i löpande summan"
+        }
+        "Partial ({})" => "Delvis ({})",
+        "Share of a full portion" => "Andel av en hel portion",
+        "Guest" => "Gäst",
+        "Guest of {}" => "Gäst hos {}",
+        "Guest who doesn't pay" => "Gäst som inte betalar",
+        "Attends meals but the share is charged to a sponsor" => {
+            "Deltar i måltider men andelen debiteras en värd"
+        }
+        "Sponsor:" => "Värd:",
+        "Paid by {}: {}" => "Betalt av {}: {}",
+        "Includes guests: {}" => "Inklusive gäster: {}",
+        "The sponsor is not in the trip, so the guest pays" => {
+            "Värden är inte med på resan, gästen betalar själv"
+        }
+        "Shared cost" => "Delad kostnad",
+        "Fixed {}" => "Fast {}",
+        "Cap {}" => "Högst {}",
+        "Fixed amount" => "Fast belopp",
+        "Capped amount" => "Maxbelopp",
+        "Amount:" => "Belopp:",
+        "Pays the attendance-based share" => "Betalar andelen enligt närvaro",
+        "Pays an agreed amount, the rest is shared by others" => {
+            "Betalar ett överenskommet belopp, resten delas av de andra"
+        }
+        "Pays the share up to an agreed maximum" => {
+            "Betalar sin andel upp till ett överenskommet maxbelopp"
+        }
+        "Share {} adjusted by {} for agreed contributions" => {
+            "Andelen {} justerad med {} för överenskomna belopp"
+        }
+        "Fixed contribution, share would be {}" => "Fast belopp, andelen skulle vara {}",
+        "Capped contribution, share would be {}" => "Begränsat belopp, andelen skulle vara {}",
+        "Split evenly" => "Dela jämnt",
+        "Split {}" => "Fördelning {}",
+        "Relative share of each serving" => "Relativ andel för varje måltid",
+        "Assign the expense to several servings to split it" => {
+            "Koppla utgiften till flera måltider för att fördela den"
+        }
+        "Nobody can take over {} left by fixed and capped contributions" => {
+            "Ingen kan ta över {} som fasta och begränsade belopp lämnar"
+        }
+        "Fixed contributions exceed the shares by {}" => "Fasta belopp överstiger andelarna med {}",
+        "The date is outside the trip" => "Datumet är utanför resan",
+        "From date" => "Enligt datum",
+        "From date ({})" => "Enligt datum ({})",
+        "No matching trip day, so it is split over all days" => {
+            "Ingen resdag matchar, så utgiften delas över alla dagar"
+        }
+        "Nobody eats these servings, so nobody pays" => {
+            "Ingen äter dessa måltider, så ingen betalar"
+        }
+        "Moekki-Calc trip report" => "Moekki-Calc resrapport",
+        "Trip dates:" => "Resans datum:",
+        "Attendance" => "Närvaro",
+        "Servings" => "Måltider",
+        "Day rates" => "Dagspriser",
+        "Day" => "Dag",
+        "Euro, Finnish (1 234,50 €)" => "Euro, finsk (1 234,50 €)",
+        "Swedish krona (1 234,50 kr)" => "Svensk krona (1 234,50 kr)",
+        "Norwegian krone (kr 1 234,50)" => "Norsk krona (kr 1 234,50)",
+        "US dollar ($1,234.50)" => "Amerikansk dollar ($1,234.50)",
+        "Guest, paid by {}: {}" => "Gäst, betalas av {}: {}",
+        "Guest of {}, who is not in the trip, so they pay" => {
+            "Gäst till {}, som inte är med på resan, så gästen betalar själv"
+        }
+        "guests {}" => "gäster {}",
+        "No days defined" => "Inga dagar",
+        "B = breakfast, L = lunch, D = dinner, S = snacks" => {
+            "Fr = frukost, Lu = lunch, Mi = middag, Me = mellanmål"
+        }
+        "{} (breakfast {}, lunch {}, dinner {}, snacks {}{})" => {
+            "{} (frukost {}, lunch {}, middag {}, mellanmål {}{})"
+        }
+        "credit" => "kreditering",
+        "estimate" => "uppskattning",
+        "Receipt" => "Kvitto",
+        "Attach receipt" => "Bifoga kvitto",
+        "Attach a photo of the receipt" => "Bifoga ett foto av kvittot",
+        "Could not attach receipt: {}" => "Kunde inte bifoga kvittot: {}",
+        "The stored image is missing or damaged" => "Den sparade bilden saknas eller är skadad",
+        "Receipts take {} MB, newer ones may not fit in storage" => {
+            "Kvitton tar {} MB, nya kanske inte ryms i lagringen"
+        }
+        "Images" => "Bilder",
+        "Replace" => "Byt ut",
+        "Search" => "Sök",
+        "Sort:" => "Sortera:",
+        "Reverse order" => "Omvänd ordning",
+        "Date added" => "Tillagd",
+        "Name" => "Namn",
+        "Price" => "Pris",
+        "Cost" => "Kostnad",
+        "All servings" => "Alla måltider",
+        "All payers" => "Alla betalare",
+        "Payer unknown" => "Betalare okänd",
+        "Paid by {}" => "Betalt av {}",
+        "Groceries" => "Livsmedel",
+        "Alcohol" => "Alkohol",
+        "Fuel" => "Bränsle",
+        "Rent" => "Hyra",
+        "Equipment" => "Utrustning",
+        "Other" => "Övrigt",
+        "Category:" => "Kategori:",
+        "Filter:" => "Filtrera:",
+        "All categories" => "Alla kategorier",
+        "Tag" => "Tagg",
+        "Tags ({})" => "Taggar ({})",
+        "e.g. breakfast run" => "t.ex. frukostinköp",
+        "By category" => "Per kategori",
+        "Refund or returned deposit that reduces the cost of its servings" => {
+            "Återbetalning eller pant som minskar kostnaden för dess måltider"
+        }
+        "Planned cost, replaced later by actual receipts" => {
+            "Planerad kostnad, ersätts senare av faktiska kvitton"
+        }
+        "No exchange rate for {}" => "Ingen växelkurs för {}",
+        "Enter the exchange rate" => "Ange växelkursen",
+        "Only compatible people pay" => "Endast lämpliga personer betalar",
+        "Only for one day" => "Endast för en dag",
+        "Expense must be assigned to at least one serving" => {
+            "Utgiften måste tilldelas minst en måltid"
+        }
+        "Budget" => "Budget",
+        "Estimated" => "Uppskattat",
+        "Actual" => "Faktiskt",
+        "Variance" => "Avvikelse",
+        "e.g. fish-free" => "t.ex. fiskfri",
+        "Add" => "Lägg till",
+        "Arrival:" => "Ankomst:",
+        "Departure:" => "Avresa:",
+        "Present for all days" => "Närvarande alla dagar",
+        "Copy attendance from" => "Kopiera närvaro från",
+        "vegetarian" => "vegetarisk",
+        "vegan" => "vegansk",
+        "gluten-free" => "glutenfri",
+        "lactose-free" => "laktosfri",
+        "nut-free" => "nötfri",
+        "Mon" => "mån",
+        "Tue" => "tis",
+        "Wed" => "ons",
+        "Thu" => "tor",
+        "Fri" => "fre",
+        "Sat" => "lör",
+        "Sun" => "sön",
+        _ => return None,
+    })
+}
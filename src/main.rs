@@ -1,6 +1,15 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
 mod app;
+mod charts;
+mod currency;
+mod diet;
+mod i18n;
+mod meal_plan;
+mod receipt;
+mod recipes;
+mod report;
+mod theme;
 mod types;
 pub use app::MoekkiCalcApp;
 
@@ -0,0 +1,79 @@
+use crate::i18n::format_number;
+use crate::types::{Day, ServingType};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MenuItem {
+    pub name: String,
+    pub serving: ServingType,
+    pub quantity_per_person: f64,
+    pub unit: String,
+    #[serde(default)]
+    pub dietary_tags: Vec<String>,
+}
+
+impl MenuItem {
+    pub fn new(name: String, serving: ServingType, quantity_per_person: f64, unit: String) -> Self {
+        Self {
+            name,
+            serving,
+            quantity_per_person,
+            unit,
+            dietary_tags: Vec::new(),
+        }
+    }
+
+    pub fn scaled_quantity(&self, day: &Day) -> f64 {
+        self.quantity_per_person * day.attendance_weight(self.serving)
+    }
+}
+
+pub struct ShoppingItem {
+    pub name: String,
+    pub unit: String,
+    pub quantity: f64,
+}
+
+pub fn shopping_list(days: &[Day]) -> Vec<ShoppingItem> {
+    let mut items: Vec<ShoppingItem> = Vec::new();
+    for d in days.iter() {
+        for m in d.menu.iter() {
+            let quantity = m.scaled_quantity(d);
+            if quantity == 0.0 {
+                continue;
+            }
+            let key = m.name.trim().to_lowercase();
+            match items
+                .iter_mut()
+                .find(|x| x.name.to_lowercase() == key && x.unit == m.unit)
+            {
+                Some(item) => item.quantity += quantity,
+                None => items.push(ShoppingItem {
+                    name: m.name.trim().to_string(),
+                    unit: m.unit.clone(),
+                    quantity,
+                }),
+            }
+        }
+    }
+    items.sort_by_key(|x| x.name.to_lowercase());
+    items
+}
+
+pub fn shopping_list_text(items: &[ShoppingItem]) -> String {
+    let mut out = String::new();
+    for i in items.iter() {
+        writeln!(out, "- {} {}", i.name, format_quantity(i.quantity, &i.unit)).unwrap();
+    }
+    out
+}
+
+pub fn format_quantity(quantity: f64, unit: &str) -> String {
+    let rounded = format_number(quantity, 2);
+    if unit.is_empty() {
+        rounded
+    } else {
+        format!("{} {}", rounded, unit)
+    }
+}
@@ -0,0 +1,130 @@
+use crate::i18n::tr;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+const EXTENSIONS: [&str; 3] = ["png", "jpg", "jpeg"];
+/// Photos are downscaled so dozens of receipts still fit in the browser's local storage.
+const MAX_SIZE: u32 = 1000;
+const JPEG_QUALITY: u8 = 70;
+/// Total size of stored images above which the user is warned about running out of storage.
+pub const STORAGE_WARNING_BYTES: usize = 2_500_000;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Receipt {
+    pub file_name: String,
+}
+
+/// Encodes a picked image as a downscaled, base64 encoded JPEG.
+pub fn encode(bytes: &[u8]) -> Result<String, String> {
+    let mut image = image::load_from_memory(bytes).map_err(|x| x.to_string())?;
+    if image.width().max(image.height()) > MAX_SIZE {
+        image = image.thumbnail(MAX_SIZE, MAX_SIZE);
+    }
+    let mut encoded = Vec::new();
+    image::DynamicImage::ImageRgb8(image.to_rgb8())
+        .write_to(
+            &mut Cursor::new(&mut encoded),
+            image::ImageOutputFormat::Jpeg(JPEG_QUALITY),
+        )
+        .map_err(|x| x.to_string())?;
+    Ok(STANDARD.encode(encoded))
+}
+
+fn storage_key(expense_id: u64) -> String {
+    format!("receipt-{}", expense_id)
+}
+
+/// Receipt images by expense id. They are stored under their own keys instead of the app
+/// state, so running out of storage only affects the receipts.
+#[derive(Default)]
+pub struct ReceiptImages {
+    images: HashMap<u64, String>,
+    changed: HashSet<u64>,
+}
+
+impl ReceiptImages {
+    pub fn load(&mut self, storage: &dyn eframe::Storage, expense_id: u64) {
+        if let Some(data) = storage.get_string(&storage_key(expense_id)) {
+            if !data.is_empty() {
+                self.images.insert(expense_id, data);
+            }
+        }
+    }
+
+    pub fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        for id in self.changed.drain() {
+            let data = self.images.get(&id).cloned().unwrap_or_default();
+            storage.set_string(&storage_key(id), data);
+        }
+    }
+
+    pub fn insert(&mut self, expense_id: u64, data: String) {
+        self.images.insert(expense_id, data);
+        self.changed.insert(expense_id);
+    }
+
+    pub fn remove(&mut self, expense_id: u64) {
+        if self.images.remove(&expense_id).is_some() {
+            self.changed.insert(expense_id);
+        }
+    }
+
+    pub fn total_size(&self) -> usize {
+        self.images.values().map(|x| x.len()).sum()
+    }
+
+    pub fn image(&self, expense_id: u64) -> Option<egui::ColorImage> {
+        let bytes = STANDARD.decode(self.images.get(&expense_id)?).ok()?;
+        let image = image::load_from_memory(&bytes).ok()?.to_rgba8();
+        let size = [image.width() as usize, image.height() as usize];
+        Some(egui::ColorImage::from_rgba_unmultiplied(
+            size,
+            image.as_flat_samples().as_slice(),
+        ))
+    }
+}
+
+pub struct PickedFile {
+    pub expense_id: u64,
+    pub file_name: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Files picked for an expense, waiting to be attached on the next frame.
+pub type PickedFiles = Arc<Mutex<Vec<PickedFile>>>;
+
+/// Opens a file dialog without blocking the UI. The picked file shows up in `picked`.
+pub fn pick(ctx: &egui::Context, expense_id: u64, picked: &PickedFiles) {
+    let ctx = ctx.clone();
+    let picked = picked.clone();
+    spawn(async move {
+        let Some(file) = rfd::AsyncFileDialog::new()
+            .add_filter(tr("Images"), &EXTENSIONS)
+            .pick_file()
+            .await
+        else {
+            return;
+        };
+        let bytes = file.read().await;
+        picked.lock().unwrap().push(PickedFile {
+            expense_id,
+            file_name: file.file_name(),
+            bytes,
+        });
+        ctx.request_repaint();
+    });
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn spawn(future: impl Future<Output = ()> + Send + 'static) {
+    std::thread::spawn(move || pollster::block_on(future));
+}
+
+#[cfg(target_arch = "wasm32")]
+fn spawn(future: impl Future<Output = ()> + 'static) {
+    wasm_bindgen_futures::spawn_local(future);
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Ingredient {
+    pub name: String,
+    pub amount_per_portion: f64,
+    pub unit: String,
+    pub unit_price: f64,
+}
+
+impl Ingredient {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            amount_per_portion: 0.0,
+            unit: String::new(),
+            unit_price: 0.0,
+        }
+    }
+
+    pub fn cost_per_portion(&self) -> f64 {
+        self.amount_per_portion * self.unit_price
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Recipe {
+    pub name: String,
+    pub ingredients: Vec<Ingredient>,
+}
+
+impl Recipe {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            ingredients: Vec::new(),
+        }
+    }
+
+    pub fn cost_per_portion(&self) -> f64 {
+        self.ingredients.iter().map(|x| x.cost_per_portion()).sum()
+    }
+}
+
+pub fn find_recipe<'a>(recipes: &'a [Recipe], name: &str) -> Option<&'a Recipe> {
+    recipes.iter().find(|x| x.name == name)
+}
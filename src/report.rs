@@ -0,0 +1,534 @@
+use crate::currency::{CurrencySettings, ExchangeRate};
+use crate::i18n::{day_label, format_date, format_short_date, tr, trf};
+use crate::types::{Contribution, Day, DayCost, Expense, Person, Servings};
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+const SERVINGS_LEGEND: &str = "B = breakfast, L = lunch, D = dinner, S = snacks";
+/// Total and serving costs, followed by the guests' costs when there are any.
+const SERVING_COSTS: &str = "{} (breakfast {}, lunch {}, dinner {}, snacks {}{})";
+const DAY_RATE_HEADINGS: [&str; 6] = ["Day", "Breakfast", "Lunch", "Dinner", "Snacks", "Total"];
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ReportFormat {
+    #[default]
+    Html,
+    Markdown,
+}
+
+pub struct Report<'a> {
+    pub start_date: NaiveDate,
+    pub days: &'a [Day],
+    pub people: &'a [Person],
+    pub expenses: &'a [Expense],
+    pub total_breakfast_cost: f64,
+    pub total_lunch_cost: f64,
+    pub total_dinner_cost: f64,
+    pub total_snacks_cost: f64,
+    pub total_cost: f64,
+    pub currency: &'a CurrencySettings,
+    pub exchange_rates: &'a [ExchangeRate],
+}
+
+impl<'a> Report<'a> {
+    pub fn generate(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Html => self.to_html(),
+            ReportFormat::Markdown => self.to_markdown(),
+        }
+    }
+
+    fn price(&self, value: f64) -> String {
+        self.currency.format(value)
+    }
+
+    fn expense_price(&self, expense: &Expense) -> String {
+        match self.currency.format_foreign(
+            expense.signed_price(),
+            &expense.currency,
+            self.exchange_rates,
+        ) {
+            Some(original) => format!("{} ({})", original, self.price(expense.base_price)),
+            None => self.price(expense.signed_price()),
+        }
+    }
+
+    fn guest_text(&self, person: &Person) -> Option<String> {
+        let sponsor = person.sponsor.as_ref()?;
+        let sponsor_found = self
+            .people
+            .iter()
+            .any(|x| &x.name == sponsor && x.sponsor.is_none());
+        Some(if sponsor_found {
+            trf(
+                "Guest, paid by {}: {}",
+                &[sponsor, &self.price(person.sponsored_cost)],
+            )
+        } else {
+            trf(
+                "Guest of {}, who is not in the trip, so they pay",
+                &[sponsor],
+            )
+        })
+    }
+
+    fn adjustment_text(&self, person: &Person) -> String {
+        let share = self.price(person.cost - person.adjustment);
+        match person.contribution {
+            Contribution::Shared => trf(
+                "Share {} adjusted by {} for agreed contributions",
+                &[&share, &self.price(person.adjustment)],
+            ),
+            Contribution::Fixed(_) => trf("Fixed contribution, share would be {}", &[&share]),
+            Contribution::Capped(_) => trf("Capped contribution, share would be {}", &[&share]),
+        }
+    }
+
+    fn guests_price(&self, cost: &DayCost) -> String {
+        if cost.guests == 0.0 {
+            String::new()
+        } else {
+            format!(", {}", trf("guests {}", &[&self.price(cost.guests)]))
+        }
+    }
+
+    fn day_date(&self, idx: usize) -> NaiveDate {
+        self.start_date + Duration::days(idx as i64)
+    }
+
+    fn trip_dates(&self) -> String {
+        match self.days.len() {
+            0 => tr("No days defined").to_string(),
+            1 => format_date(self.start_date),
+            len => format!(
+                "{} – {}",
+                format_date(self.start_date),
+                format_date(self.day_date(len - 1))
+            ),
+        }
+    }
+
+    fn day_heading(&self, idx: usize, day: &Day) -> String {
+        format!(
+            "{} ({})",
+            day_label(&day.name),
+            format_short_date(self.day_date(idx))
+        )
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "# {}", tr("Moekki-Calc trip report")).unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "**{}** {}", tr("Trip dates:"), self.trip_dates()).unwrap();
+        writeln!(out).unwrap();
+
+        writeln!(out, "## {}", tr("Attendance")).unwrap();
+        writeln!(out).unwrap();
+        write!(out, "| {} |", tr("Person")).unwrap();
+        for (idx, d) in self.days.iter().enumerate() {
+            write!(out, " {} |", self.day_heading(idx, d)).unwrap();
+        }
+        writeln!(out).unwrap();
+        write!(out, "|---|").unwrap();
+        for _ in self.days.iter() {
+            write!(out, "---|").unwrap();
+        }
+        writeln!(out).unwrap();
+        for p in self.people.iter() {
+            write!(out, "| {} |", escape_markdown(&p.name)).unwrap();
+            for a in p.attendance.iter() {
+                if a.present {
+                    write!(out, " {} |", servings_short(&a.servings)).unwrap();
+                } else {
+                    write!(out, " – |").unwrap();
+                }
+            }
+            writeln!(out).unwrap();
+        }
+        writeln!(out).unwrap();
+        writeln!(out, "{}", tr(SERVINGS_LEGEND)).unwrap();
+        writeln!(out).unwrap();
+
+        writeln!(out, "## {}", tr("Expenses")).unwrap();
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "| {} | {} | {} |",
+            tr("Expense"),
+            tr("Servings"),
+            tr("Price")
+        )
+        .unwrap();
+        writeln!(out, "|---|---|---:|").unwrap();
+        for e in self.expenses.iter() {
+            writeln!(
+                out,
+                "| {} | {} | {} |",
+                escape_markdown(&expense_name(e)),
+                servings_long(&e.serving_type),
+                self.expense_price(e)
+            )
+            .unwrap();
+        }
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "{}: {}",
+            tr("Total"),
+            trf(
+                SERVING_COSTS,
+                &[
+                    &format!("**{}**", self.price(self.total_cost)),
+                    &self.price(self.total_breakfast_cost),
+                    &self.price(self.total_lunch_cost),
+                    &self.price(self.total_dinner_cost),
+                    &self.price(self.total_snacks_cost),
+                    &"",
+                ]
+            )
+        )
+        .unwrap();
+        writeln!(out).unwrap();
+
+        writeln!(out, "## {}", tr("Day rates")).unwrap();
+        writeln!(out).unwrap();
+        write!(out, "|").unwrap();
+        for heading in DAY_RATE_HEADINGS {
+            write!(out, " {} |", tr(heading)).unwrap();
+        }
+        writeln!(out).unwrap();
+        writeln!(out, "|---|---:|---:|---:|---:|---:|").unwrap();
+        for (idx, d) in self.days.iter().enumerate() {
+            writeln!(
+                out,
+                "| {} | {} | {} | {} | {} | {} |",
+                self.day_heading(idx, d),
+                self.price(d.breakfast_day_rate),
+                self.price(d.lunch_day_rate),
+                self.price(d.dinner_day_rate),
+                self.price(d.snacks_day_rate),
+                self.price(d.total_day_rate)
+            )
+            .unwrap();
+        }
+        writeln!(out).unwrap();
+
+        writeln!(out, "## {}", tr("Balances")).unwrap();
+        for p in self.people.iter() {
+            writeln!(out).unwrap();
+            writeln!(
+                out,
+                "### {}: {}",
+                escape_markdown(&p.name),
+                self.price(p.cost)
+            )
+            .unwrap();
+            writeln!(out).unwrap();
+            if let Some(text) = self.guest_text(p) {
+                writeln!(out, "{}", escape_markdown(&text)).unwrap();
+                writeln!(out).unwrap();
+            }
+            if p.adjustment != 0.0 {
+                writeln!(out, "{}", self.adjustment_text(p)).unwrap();
+                writeln!(out).unwrap();
+            }
+            for (idx, (c, d)) in p.breakdown.iter().zip(self.days.iter()).enumerate() {
+                if c.total() == 0.0 {
+                    continue;
+                }
+                writeln!(
+                    out,
+                    "- {}: {}",
+                    self.day_heading(idx, d),
+                    trf(
+                        SERVING_COSTS,
+                        &[
+                            &self.price(c.total()),
+                            &self.price(c.breakfast),
+                            &self.price(c.lunch),
+                            &self.price(c.dinner),
+                            &self.price(c.snacks),
+                            &self.guests_price(c),
+                        ]
+                    )
+                )
+                .unwrap();
+            }
+        }
+        writeln!(out).unwrap();
+        let covered: f64 = self.people.iter().map(|x| x.cost).sum();
+        writeln!(
+            out,
+            "{}",
+            trf(
+                "Expenses covered: {} / {}",
+                &[&self.price(covered), &self.price(self.total_cost)]
+            )
+        )
+        .unwrap();
+        out
+    }
+
+    fn to_html(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "<!DOCTYPE html>").unwrap();
+        writeln!(out, "<html>").unwrap();
+        writeln!(out, "<head>").unwrap();
+        writeln!(out, "<meta charset=\"utf-8\">").unwrap();
+        writeln!(
+            out,
+            "<title>{}</title>",
+            escape_html(tr("Moekki-Calc trip report"))
+        )
+        .unwrap();
+        writeln!(out, "<style>").unwrap();
+        writeln!(
+            out,
+            "body {{ font-family: sans-serif; margin: 2em; color: #222; }}"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "table {{ border-collapse: collapse; margin-bottom: 1em; }}"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "th, td {{ border: 1px solid #999; padding: 4px 8px; text-align: left; }}"
+        )
+        .unwrap();
+        writeln!(out, "td.num {{ text-align: right; }}").unwrap();
+        writeln!(out, "</style>").unwrap();
+        writeln!(out, "</head>").unwrap();
+        writeln!(out, "<body>").unwrap();
+        writeln!(
+            out,
+            "<h1>{}</h1>",
+            escape_html(tr("Moekki-Calc trip report"))
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "<p><strong>{}</strong> {}</p>",
+            escape_html(tr("Trip dates:")),
+            escape_html(&self.trip_dates())
+        )
+        .unwrap();
+
+        writeln!(out, "<h2>{}</h2>", escape_html(tr("Attendance"))).unwrap();
+        writeln!(out, "<table>").unwrap();
+        write!(out, "<tr><th>{}</th>", escape_html(tr("Person"))).unwrap();
+        for (idx, d) in self.days.iter().enumerate() {
+            write!(out, "<th>{}</th>", escape_html(&self.day_heading(idx, d))).unwrap();
+        }
+        writeln!(out, "</tr>").unwrap();
+        for p in self.people.iter() {
+            write!(out, "<tr><td>{}</td>", escape_html(&p.name)).unwrap();
+            for a in p.attendance.iter() {
+                if a.present {
+                    write!(out, "<td>{}</td>", servings_short(&a.servings)).unwrap();
+                } else {
+                    write!(out, "<td>–</td>").unwrap();
+                }
+            }
+            writeln!(out, "</tr>").unwrap();
+        }
+        writeln!(out, "</table>").unwrap();
+        writeln!(out, "<p>{}</p>", escape_html(tr(SERVINGS_LEGEND))).unwrap();
+
+        writeln!(out, "<h2>{}</h2>", escape_html(tr("Expenses"))).unwrap();
+        writeln!(out, "<table>").unwrap();
+        writeln!(
+            out,
+            "<tr><th>{}</th><th>{}</th><th>{}</th></tr>",
+            escape_html(tr("Expense")),
+            escape_html(tr("Servings")),
+            escape_html(tr("Price"))
+        )
+        .unwrap();
+        for e in self.expenses.iter() {
+            writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td></tr>",
+                escape_html(&expense_name(e)),
+                servings_long(&e.serving_type),
+                escape_html(&self.expense_price(e))
+            )
+            .unwrap();
+        }
+        writeln!(out, "</table>").unwrap();
+        writeln!(
+            out,
+            "<p>{}: {}</p>",
+            escape_html(tr("Total")),
+            trf(
+                SERVING_COSTS,
+                &[
+                    &format!(
+                        "<strong>{}</strong>",
+                        escape_html(&self.price(self.total_cost))
+                    ),
+                    &escape_html(&self.price(self.total_breakfast_cost)),
+                    &escape_html(&self.price(self.total_lunch_cost)),
+                    &escape_html(&self.price(self.total_dinner_cost)),
+                    &escape_html(&self.price(self.total_snacks_cost)),
+                    &"",
+                ]
+            )
+        )
+        .unwrap();
+
+        writeln!(out, "<h2>{}</h2>", escape_html(tr("Day rates"))).unwrap();
+        writeln!(out, "<table>").unwrap();
+        write!(out, "<tr>").unwrap();
+        for heading in DAY_RATE_HEADINGS {
+            write!(out, "<th>{}</th>", escape_html(tr(heading))).unwrap();
+        }
+        writeln!(out, "</tr>").unwrap();
+        for (idx, d) in self.days.iter().enumerate() {
+            write!(
+                out,
+                "<tr><td>{}</td>",
+                escape_html(&self.day_heading(idx, d))
+            )
+            .unwrap();
+            for rate in [
+                d.breakfast_day_rate,
+                d.lunch_day_rate,
+                d.dinner_day_rate,
+                d.snacks_day_rate,
+                d.total_day_rate,
+            ] {
+                write!(
+                    out,
+                    "<td class=\"num\">{}</td>",
+                    escape_html(&self.price(rate))
+                )
+                .unwrap();
+            }
+            writeln!(out, "</tr>").unwrap();
+        }
+        writeln!(out, "</table>").unwrap();
+
+        writeln!(out, "<h2>{}</h2>", escape_html(tr("Balances"))).unwrap();
+        for p in self.people.iter() {
+            writeln!(
+                out,
+                "<h3>{}: {}</h3>",
+                escape_html(&p.name),
+                escape_html(&self.price(p.cost))
+            )
+            .unwrap();
+            if let Some(text) = self.guest_text(p) {
+                writeln!(out, "<p>{}</p>", escape_html(&text)).unwrap();
+            }
+            if p.adjustment != 0.0 {
+                writeln!(out, "<p>{}</p>", escape_html(&self.adjustment_text(p))).unwrap();
+            }
+            writeln!(out, "<ul>").unwrap();
+            for (idx, (c, d)) in p.breakdown.iter().zip(self.days.iter()).enumerate() {
+                if c.total() == 0.0 {
+                    continue;
+                }
+                let costs = trf(
+                    SERVING_COSTS,
+                    &[
+                        &self.price(c.total()),
+                        &self.price(c.breakfast),
+                        &self.price(c.lunch),
+                        &self.price(c.dinner),
+                        &self.price(c.snacks),
+                        &self.guests_price(c),
+                    ],
+                );
+                writeln!(
+                    out,
+                    "<li>{}: {}</li>",
+                    escape_html(&self.day_heading(idx, d)),
+                    escape_html(&costs)
+                )
+                .unwrap();
+            }
+            writeln!(out, "</ul>").unwrap();
+        }
+        let covered: f64 = self.people.iter().map(|x| x.cost).sum();
+        writeln!(
+            out,
+            "<p>{}</p>",
+            escape_html(&trf(
+                "Expenses covered: {} / {}",
+                &[&self.price(covered), &self.price(self.total_cost)]
+            ))
+        )
+        .unwrap();
+        writeln!(out, "</body>").unwrap();
+        writeln!(out, "</html>").unwrap();
+        out
+    }
+}
+
+fn expense_name(expense: &Expense) -> String {
+    let mut name = expense.name.clone();
+    if expense.is_credit() {
+        name.push_str(&format!(" ({})", tr("credit")));
+    }
+    if expense.estimate {
+        name.push_str(&format!(" ({})", tr("estimate")));
+    }
+    name
+}
+
+fn servings_short(servings: &Servings) -> String {
+    let mut parts = Vec::new();
+    if servings.breakfast {
+        parts.push(tr("B"));
+    }
+    if servings.lunch {
+        parts.push(tr("L"));
+    }
+    if servings.dinner {
+        parts.push(tr("D"));
+    }
+    if servings.snacks {
+        parts.push(tr("S"));
+    }
+    if parts.is_empty() {
+        String::from("–")
+    } else {
+        parts.join(" ")
+    }
+}
+
+fn servings_long(servings: &Servings) -> String {
+    let mut parts = Vec::new();
+    if servings.breakfast {
+        parts.push(tr("Breakfast"));
+    }
+    if servings.lunch {
+        parts.push(tr("Lunch"));
+    }
+    if servings.dinner {
+        parts.push(tr("Dinner"));
+    }
+    if servings.snacks {
+        parts.push(tr("Snacks"));
+    }
+    if parts.is_empty() {
+        String::from("–")
+    } else {
+        parts.join(", ")
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|")
+}
@@ -0,0 +1,188 @@
+use crate::i18n::tr;
+use egui::{epaint::Color32, Stroke, Visuals};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeMode {
+    #[default]
+    System,
+    Light,
+    Dark,
+    HighContrast,
+}
+
+impl ThemeMode {
+    pub const ALL: [ThemeMode; 4] = [
+        ThemeMode::System,
+        ThemeMode::Light,
+        ThemeMode::Dark,
+        ThemeMode::HighContrast,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            ThemeMode::System => "System",
+            ThemeMode::Light => "Light",
+            ThemeMode::Dark => "Dark",
+            ThemeMode::HighContrast => "High contrast",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Accent {
+    #[default]
+    Teal,
+    Blue,
+    Green,
+    Orange,
+    Purple,
+    Pink,
+}
+
+impl Accent {
+    pub const ALL: [Accent; 6] = [
+        Accent::Teal,
+        Accent::Blue,
+        Accent::Green,
+        Accent::Orange,
+        Accent::Purple,
+        Accent::Pink,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            Accent::Teal => "Teal",
+            Accent::Blue => "Blue",
+            Accent::Green => "Green",
+            Accent::Orange => "Orange",
+            Accent::Purple => "Purple",
+            Accent::Pink => "Pink",
+        })
+    }
+
+    pub fn color(&self) -> Color32 {
+        match self {
+            Accent::Teal => Color32::from_rgb(40, 150, 170),
+            Accent::Blue => Color32::from_rgb(60, 120, 220),
+            Accent::Green => Color32::from_rgb(60, 160, 80),
+            Accent::Orange => Color32::from_rgb(230, 130, 40),
+            Accent::Purple => Color32::from_rgb(140, 90, 210),
+            Accent::Pink => Color32::from_rgb(220, 80, 150),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub mode: ThemeMode,
+    pub accent: Accent,
+}
+
+impl Theme {
+    pub fn visuals(&self, system_dark: bool) -> Visuals {
+        let mut visuals = match self.mode {
+            ThemeMode::System if system_dark => Visuals::dark(),
+            ThemeMode::System | ThemeMode::Light => Visuals::light(),
+            ThemeMode::Dark => Visuals::dark(),
+            ThemeMode::HighContrast => high_contrast(),
+        };
+        let accent = self.accent.color();
+        if visuals.dark_mode {
+            visuals.selection.bg_fill = mix(accent, Color32::BLACK, 0.35);
+            visuals.selection.stroke = Stroke::new(1.0, mix(accent, Color32::WHITE, 0.7));
+            visuals.hyperlink_color = mix(accent, Color32::WHITE, 0.3);
+        } else {
+            visuals.selection.bg_fill = mix(accent, Color32::WHITE, 0.55);
+            visuals.selection.stroke = Stroke::new(1.0, mix(accent, Color32::BLACK, 0.5));
+            visuals.hyperlink_color = mix(accent, Color32::BLACK, 0.2);
+        }
+        if self.mode == ThemeMode::HighContrast {
+            visuals.selection.bg_fill = accent;
+            visuals.selection.stroke = Stroke::new(2.0, Color32::WHITE);
+        }
+        visuals
+    }
+
+    pub fn apply(&self, ctx: &egui::Context, system_theme: Option<eframe::Theme>) {
+        let system_dark = system_theme != Some(eframe::Theme::Light);
+        let visuals = self.visuals(system_dark);
+        if ctx.style().visuals != visuals {
+            ctx.set_visuals(visuals);
+        }
+    }
+}
+
+/// Colors of the custom painted parts of the UI, derived from the active visuals.
+pub struct Palette {
+    pub background: Color32,
+    pub frame_stroke: Stroke,
+    pub over_budget: Color32,
+    pub under_budget: Color32,
+    pub credit: Color32,
+}
+
+impl Palette {
+    pub fn new(visuals: &Visuals) -> Self {
+        let high_contrast = visuals.extreme_bg_color == Color32::BLACK;
+        let background = if high_contrast {
+            visuals.extreme_bg_color
+        } else {
+            mix(visuals.extreme_bg_color, visuals.selection.bg_fill, 0.12)
+        };
+        let frame_stroke = if high_contrast {
+            Stroke::new(2.0, visuals.text_color())
+        } else {
+            Stroke::new(1.0, visuals.weak_text_color())
+        };
+        let under_budget = if visuals.dark_mode {
+            Color32::from_rgb(90, 210, 90)
+        } else {
+            Color32::from_rgb(0, 120, 0)
+        };
+        Self {
+            background,
+            frame_stroke,
+            over_budget: visuals.error_fg_color,
+            under_budget,
+            credit: under_budget,
+        }
+    }
+}
+
+fn high_contrast() -> Visuals {
+    let mut visuals = Visuals::dark();
+    visuals.override_text_color = Some(Color32::WHITE);
+    visuals.panel_fill = Color32::BLACK;
+    visuals.window_fill = Color32::BLACK;
+    visuals.extreme_bg_color = Color32::BLACK;
+    visuals.faint_bg_color = Color32::from_gray(40);
+    visuals.window_stroke = Stroke::new(2.0, Color32::WHITE);
+    visuals.error_fg_color = Color32::from_rgb(255, 90, 90);
+    visuals.warn_fg_color = Color32::from_rgb(255, 210, 0);
+    for widget in [
+        &mut visuals.widgets.noninteractive,
+        &mut visuals.widgets.inactive,
+        &mut visuals.widgets.hovered,
+        &mut visuals.widgets.active,
+        &mut visuals.widgets.open,
+    ] {
+        widget.bg_stroke = Stroke::new(1.0, Color32::WHITE);
+        widget.fg_stroke = Stroke::new(1.5, Color32::WHITE);
+    }
+    visuals.widgets.noninteractive.bg_fill = Color32::BLACK;
+    visuals.widgets.noninteractive.weak_bg_fill = Color32::BLACK;
+    visuals.widgets.inactive.bg_fill = Color32::from_gray(20);
+    visuals.widgets.inactive.weak_bg_fill = Color32::from_gray(20);
+    visuals
+}
+
+fn mix(a: Color32, b: Color32, t: f32) -> Color32 {
+    let channel = |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * t).round() as u8;
+    Color32::from_rgb(
+        channel(a.r(), b.r()),
+        channel(a.g(), b.g()),
+        channel(a.b(), b.b()),
+    )
+}
@@ -1,3 +1,7 @@
+use crate::i18n::tr;
+use crate::meal_plan::MenuItem;
+use crate::receipt::Receipt;
+use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -13,6 +17,18 @@ pub struct Day {
     pub dinner_attendance_count: usize,
     pub snacks_day_rate: f64,
     pub snacks_attendance_count: usize,
+    #[serde(default)]
+    pub breakfast_attendance_weight: f64,
+    #[serde(default)]
+    pub lunch_attendance_weight: f64,
+    #[serde(default)]
+    pub dinner_attendance_weight: f64,
+    #[serde(default)]
+    pub snacks_attendance_weight: f64,
+    #[serde(default)]
+    pub estimated_rates: ServingCosts,
+    #[serde(default)]
+    pub menu: Vec<MenuItem>,
 }
 
 impl Day {
@@ -29,11 +45,102 @@ impl Day {
             dinner_attendance_count: 0,
             snacks_day_rate: 0.0,
             snacks_attendance_count: 0,
+            breakfast_attendance_weight: 0.0,
+            lunch_attendance_weight: 0.0,
+            dinner_attendance_weight: 0.0,
+            snacks_attendance_weight: 0.0,
+            estimated_rates: ServingCosts::default(),
+            menu: Vec::new(),
+        }
+    }
+
+    /// Sum of the attendance weights of everyone eating the serving.
+    pub fn attendance_weight(&self, serving: ServingType) -> f64 {
+        if !self.servings.get(serving) {
+            return 0.0;
+        }
+        match serving {
+            ServingType::Breakfast => self.breakfast_attendance_weight,
+            ServingType::Lunch => self.lunch_attendance_weight,
+            ServingType::Dinner => self.dinner_attendance_weight,
+            ServingType::Snacks => self.snacks_attendance_weight,
         }
     }
 }
 
-#[derive(Default, Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ServingType {
+    Breakfast,
+    Lunch,
+    Dinner,
+    Snacks,
+}
+
+impl ServingType {
+    pub const ALL: [ServingType; 4] = [
+        ServingType::Breakfast,
+        ServingType::Lunch,
+        ServingType::Dinner,
+        ServingType::Snacks,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            ServingType::Breakfast => "Breakfast",
+            ServingType::Lunch => "Lunch",
+            ServingType::Dinner => "Dinner",
+            ServingType::Snacks => "Snacks",
+        })
+    }
+
+    pub fn short_name(&self) -> &'static str {
+        tr(match self {
+            ServingType::Breakfast => "B",
+            ServingType::Lunch => "L",
+            ServingType::Dinner => "D",
+            ServingType::Snacks => "S",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Category {
+    #[default]
+    Groceries,
+    Alcohol,
+    Fuel,
+    Rent,
+    Equipment,
+    Other,
+}
+
+impl Category {
+    pub const ALL: [Category; 6] = [
+        Category::Groceries,
+        Category::Alcohol,
+        Category::Fuel,
+        Category::Rent,
+        Category::Equipment,
+        Category::Other,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            Category::Groceries => "Groceries",
+            Category::Alcohol => "Alcohol",
+            Category::Fuel => "Fuel",
+            Category::Rent => "Rent",
+            Category::Equipment => "Equipment",
+            Category::Other => "Other",
+        })
+    }
+
+    pub fn is_food(&self) -> bool {
+        matches!(self, Category::Groceries)
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct Servings {
     pub breakfast: bool,
     pub lunch: bool,
@@ -50,34 +157,290 @@ impl Servings {
             snacks: true,
         }
     }
+
+    pub fn get(&self, serving: ServingType) -> bool {
+        match serving {
+            ServingType::Breakfast => self.breakfast,
+            ServingType::Lunch => self.lunch,
+            ServingType::Dinner => self.dinner,
+            ServingType::Snacks => self.snacks,
+        }
+    }
+
+    pub fn set(&mut self, serving: ServingType, value: bool) {
+        match serving {
+            ServingType::Breakfast => self.breakfast = value,
+            ServingType::Lunch => self.lunch = value,
+            ServingType::Dinner => self.dinner = value,
+            ServingType::Snacks => self.snacks = value,
+        }
+    }
+}
+
+/// Relative weights for dividing an expense between the servings it is assigned to.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServingSplit {
+    pub breakfast: f64,
+    pub lunch: f64,
+    pub dinner: f64,
+    pub snacks: f64,
+}
+
+impl Default for ServingSplit {
+    fn default() -> Self {
+        Self {
+            breakfast: 1.0,
+            lunch: 1.0,
+            dinner: 1.0,
+            snacks: 1.0,
+        }
+    }
+}
+
+impl ServingSplit {
+    pub fn get(&self, serving: ServingType) -> f64 {
+        match serving {
+            ServingType::Breakfast => self.breakfast,
+            ServingType::Lunch => self.lunch,
+            ServingType::Dinner => self.dinner,
+            ServingType::Snacks => self.snacks,
+        }
+    }
+
+    pub fn get_mut(&mut self, serving: ServingType) -> &mut f64 {
+        match serving {
+            ServingType::Breakfast => &mut self.breakfast,
+            ServingType::Lunch => &mut self.lunch,
+            ServingType::Dinner => &mut self.dinner,
+            ServingType::Snacks => &mut self.snacks,
+        }
+    }
+
+    /// Share of the price falling on the serving, evenly split if all weights are zero.
+    pub fn ratio(&self, serving: ServingType, servings: &Servings) -> f64 {
+        if !servings.get(serving) {
+            return 0.0;
+        }
+        let flagged: Vec<ServingType> = ServingType::ALL
+            .into_iter()
+            .filter(|x| servings.get(*x))
+            .collect();
+        let total: f64 = flagged.iter().map(|x| self.get(*x).max(0.0)).sum();
+        if total > 0.0 {
+            self.get(serving).max(0.0) / total
+        } else {
+            1.0 / flagged.len() as f64
+        }
+    }
+
+    pub fn is_even(&self, servings: &Servings) -> bool {
+        let ratios: Vec<f64> = ServingType::ALL
+            .into_iter()
+            .filter(|x| servings.get(*x))
+            .map(|x| self.ratio(x, servings))
+            .collect();
+        ratios.windows(2).all(|x| (x[0] - x[1]).abs() < 1e-9)
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct ServingCosts {
+    pub total: f64,
+    pub breakfast: f64,
+    pub lunch: f64,
+    pub dinner: f64,
+    pub snacks: f64,
+}
+
+impl ServingCosts {
+    pub fn get(&self, serving: ServingType) -> f64 {
+        match serving {
+            ServingType::Breakfast => self.breakfast,
+            ServingType::Lunch => self.lunch,
+            ServingType::Dinner => self.dinner,
+            ServingType::Snacks => self.snacks,
+        }
+    }
+
+    pub fn add(&mut self, price: f64, servings: &Servings, split: &ServingSplit) {
+        self.total += price;
+        self.breakfast += price * split.ratio(ServingType::Breakfast, servings);
+        self.lunch += price * split.ratio(ServingType::Lunch, servings);
+        self.dinner += price * split.ratio(ServingType::Dinner, servings);
+        self.snacks += price * split.ratio(ServingType::Snacks, servings);
+    }
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct Expense {
+    /// Stable identifier, unlike the position in the expense list.
+    #[serde(default)]
+    pub id: u64,
     pub name: String,
     pub price: f64,
+    #[serde(default)]
+    pub currency: String,
+    #[serde(default)]
+    pub base_price: f64,
+    #[serde(default)]
+    pub estimate: bool,
     pub serving_type: Servings,
     pub specific_day: bool,
     pub target_day: String,
+    #[serde(default)]
+    pub recipe: Option<String>,
+    #[serde(default)]
+    pub dietary_tags: Vec<String>,
+    #[serde(default)]
+    pub exclude_incompatible: bool,
+    #[serde(default)]
+    pub credit: bool,
+    #[serde(default)]
+    pub category: Category,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub paid_by: String,
+    #[serde(default)]
+    pub receipt: Option<Receipt>,
+    #[serde(default)]
+    pub incurred: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub split: ServingSplit,
 }
 
 impl Expense {
-    pub fn new(name: String, price: f64) -> Self {
+    pub fn new(id: u64, name: String, price: f64, currency: String) -> Self {
         Self {
+            id,
             name,
             price,
+            currency,
+            base_price: 0.0,
+            estimate: false,
             serving_type: Servings::default(),
             specific_day: false,
             target_day: String::new(),
+            recipe: None,
+            dietary_tags: Vec::new(),
+            exclude_incompatible: false,
+            credit: false,
+            category: Category::default(),
+            tags: Vec::new(),
+            paid_by: String::new(),
+            receipt: None,
+            incurred: None,
+            split: ServingSplit::default(),
+        }
+    }
+
+    /// Index of the trip day the expense was incurred on, if it falls within the trip.
+    pub fn inferred_day(&self, start_date: NaiveDate, day_count: usize) -> Option<usize> {
+        let offset = (self.incurred?.date() - start_date).num_days();
+        (0..day_count as i64)
+            .contains(&offset)
+            .then_some(offset as usize)
+    }
+
+    /// Servings the expense is charged to over the given days. Servings nobody eats on those
+    /// days fall back to the ones that are eaten, and None means nobody can be charged.
+    pub fn charged_servings(&self, days: &[Day]) -> Option<Servings> {
+        let eaten = |x: ServingType| days.iter().any(|d| d.attendance_weight(x) > 0.0);
+        let flagged = ServingType::ALL
+            .into_iter()
+            .any(|x| self.serving_type.get(x));
+        let mut servings = Servings::default();
+        for x in ServingType::ALL {
+            servings.set(x, self.serving_type.get(x) && eaten(x));
+        }
+        if flagged && !ServingType::ALL.into_iter().any(|x| servings.get(x)) {
+            for x in ServingType::ALL {
+                servings.set(x, eaten(x));
+            }
+        }
+        ServingType::ALL
+            .into_iter()
+            .any(|x| servings.get(x))
+            .then_some(servings)
+    }
+
+    /// Index of the day a one-day expense belongs to: the chosen day, or the day of its
+    /// date when no day is chosen.
+    pub fn target_day_index(&self, days: &[Day], start_date: NaiveDate) -> Option<usize> {
+        if !self.specific_day {
+            None
+        } else if self.target_day.is_empty() {
+            self.inferred_day(start_date, days.len())
+        } else {
+            days.iter().position(|x| x.name == self.target_day)
+        }
+    }
+
+    pub fn is_credit(&self) -> bool {
+        self.credit || self.price < 0.0
+    }
+
+    pub fn has_tag_matching(&self, filter: &str) -> bool {
+        let filter = filter.trim().to_lowercase();
+        filter.is_empty() || self.tags.iter().any(|x| x.contains(&filter))
+    }
+
+    pub fn signed_price(&self) -> f64 {
+        if self.credit {
+            -self.price.abs()
+        } else {
+            self.price
         }
     }
 }
 
-#[derive(Serialize, Deserialize)]
+/// Optional share of a full portion per serving, for partial presence or half portions.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Fractions {
+    pub breakfast: Option<f64>,
+    pub lunch: Option<f64>,
+    pub dinner: Option<f64>,
+    pub snacks: Option<f64>,
+}
+
+impl Fractions {
+    pub const MIN: f64 = 0.1;
+
+    pub fn get_mut(&mut self, serving: ServingType) -> &mut Option<f64> {
+        match serving {
+            ServingType::Breakfast => &mut self.breakfast,
+            ServingType::Lunch => &mut self.lunch,
+            ServingType::Dinner => &mut self.dinner,
+            ServingType::Snacks => &mut self.snacks,
+        }
+    }
+
+    pub fn get(&self, serving: ServingType) -> Option<f64> {
+        match serving {
+            ServingType::Breakfast => self.breakfast,
+            ServingType::Lunch => self.lunch,
+            ServingType::Dinner => self.dinner,
+            ServingType::Snacks => self.snacks,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        ServingType::ALL
+            .iter()
+            .filter(|x| self.get(**x).is_some())
+            .count()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Attendance {
     pub day_name: String,
     pub present: bool,
     pub servings: Servings,
+    #[serde(default)]
+    pub fractions: Fractions,
 }
 
 impl Attendance {
@@ -86,31 +449,361 @@ impl Attendance {
             day_name,
             present: false,
             servings: Servings::new(),
+            fractions: Fractions::default(),
         }
     }
+
+    pub fn attends(&self, serving: ServingType) -> bool {
+        self.present && self.servings.get(serving)
+    }
+
+    /// Weight used when dividing the serving's day rate, 0 when not attending.
+    pub fn weight(&self, serving: ServingType) -> f64 {
+        if !self.attends(serving) {
+            return 0.0;
+        }
+        self.fractions
+            .get(serving)
+            .map_or(1.0, |x| x.clamp(Fractions::MIN, 1.0))
+    }
+
+    pub fn set_attends(&mut self, serving: ServingType, value: bool) {
+        if value && !self.present {
+            self.present = true;
+            self.servings = Servings::default();
+        }
+        self.servings.set(serving, value);
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct DayCost {
+    pub day_name: String,
+    pub breakfast: f64,
+    pub lunch: f64,
+    pub dinner: f64,
+    pub snacks: f64,
+    /// Share of sponsored guests charged to this person.
+    #[serde(default)]
+    pub guests: f64,
+}
+
+impl DayCost {
+    pub fn new(day_name: String) -> Self {
+        Self {
+            day_name,
+            ..Default::default()
+        }
+    }
+
+    pub fn get_mut(&mut self, serving: ServingType) -> &mut f64 {
+        match serving {
+            ServingType::Breakfast => &mut self.breakfast,
+            ServingType::Lunch => &mut self.lunch,
+            ServingType::Dinner => &mut self.dinner,
+            ServingType::Snacks => &mut self.snacks,
+        }
+    }
+
+    pub fn total(&self) -> f64 {
+        self.breakfast + self.lunch + self.dinner + self.snacks + self.guests
+    }
+}
+
+/// How much a person pays, compared to their attendance-based share.
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum Contribution {
+    #[default]
+    Shared,
+    Fixed(f64),
+    Capped(f64),
+}
+
+impl Contribution {
+    pub fn amount(&self) -> Option<f64> {
+        match self {
+            Contribution::Shared => None,
+            Contribution::Fixed(x) | Contribution::Capped(x) => Some(*x),
+        }
+    }
+
+    /// The agreed cost for the given share, before anyone takes over a remainder.
+    pub fn agreed_cost(&self, share: f64) -> f64 {
+        match self {
+            Contribution::Shared => share,
+            Contribution::Fixed(x) => *x,
+            Contribution::Capped(x) => share.min(*x),
+        }
+    }
+
+    /// Costs of everyone given their contributions and attendance-based shares. The
+    /// remainder left by fixed and capped people is divided among the others by their
+    /// shares, up to their caps. Also returns the part of the remainder nobody can take.
+    pub fn settle(people: &[(Contribution, f64)]) -> (Vec<f64>, f64) {
+        let mut costs: Vec<f64> = people.iter().map(|(c, x)| c.agreed_cost(*x)).collect();
+        let mut remainder: f64 = people.iter().zip(costs.iter()).map(|(p, c)| p.1 - c).sum();
+        // Every round either moves the whole remainder or fills someone up to their cap.
+        for _ in 0..=people.len() {
+            if remainder.abs() < 1e-9 {
+                return (costs, 0.0);
+            }
+            let receivers: Vec<usize> = (0..people.len())
+                .filter(|x| match people[*x].0 {
+                    Contribution::Shared => true,
+                    Contribution::Fixed(_) => false,
+                    Contribution::Capped(cap) => remainder < 0.0 || costs[*x] < cap,
+                })
+                .collect();
+            if receivers.is_empty() {
+                break;
+            }
+            let total: f64 = receivers.iter().map(|x| people[*x].1.max(0.0)).sum();
+            let mut moved = 0.0;
+            for idx in receivers.iter() {
+                let weight = if total > 0.0 {
+                    people[*idx].1.max(0.0) / total
+                } else {
+                    1.0 / receivers.len() as f64
+                };
+                let mut cost = costs[*idx] + remainder * weight;
+                if let Contribution::Capped(cap) = people[*idx].0 {
+                    cost = cost.min(cap);
+                }
+                moved += cost - costs[*idx];
+                costs[*idx] = cost;
+            }
+            remainder -= moved;
+        }
+        (costs, remainder)
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Stay {
+    pub arrival_day: usize,
+    pub arrival_serving: ServingType,
+    pub departure_day: usize,
+    pub departure_serving: ServingType,
+}
+
+impl Stay {
+    pub fn new(days: &[Day]) -> Self {
+        Self {
+            arrival_day: 0,
+            arrival_serving: ServingType::Breakfast,
+            departure_day: days.len().saturating_sub(1),
+            departure_serving: ServingType::Snacks,
+        }
+    }
+
+    pub fn includes(&self, day: usize, serving: ServingType) -> bool {
+        (self.arrival_day, self.arrival_serving) <= (day, serving)
+            && (day, serving) <= (self.departure_day, self.departure_serving)
+    }
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct Person {
     pub name: String,
     pub attendance: Vec<Attendance>,
+    #[serde(default)]
+    pub dietary_tags: Vec<String>,
+    #[serde(default)]
+    pub stay: Option<Stay>,
     pub cost: f64,
+    #[serde(default)]
+    pub estimated_cost: f64,
+    #[serde(default)]
+    pub breakdown: Vec<DayCost>,
+    /// Name of the person paying for this guest.
+    #[serde(default)]
+    pub sponsor: Option<String>,
+    /// Cost of a guest charged to the sponsor instead.
+    #[serde(default)]
+    pub sponsored_cost: f64,
+    #[serde(default)]
+    pub contribution: Contribution,
+    /// Difference between the cost and the attendance-based share.
+    #[serde(default)]
+    pub adjustment: f64,
 }
 
 impl Person {
     pub fn new(name: String, days: &[Day]) -> Self {
         let mut attendance: Vec<Attendance> = Vec::new();
         for d in days.iter() {
-            attendance.push(Attendance {
-                day_name: d.name.clone(),
-                present: false,
-                servings: Servings::new(),
-            });
+            attendance.push(Attendance::new(d.name.clone()));
         }
         Self {
             name,
             attendance,
+            dietary_tags: Vec::new(),
+            stay: None,
             cost: 0.0,
+            estimated_cost: 0.0,
+            breakdown: Vec::new(),
+            sponsor: None,
+            sponsored_cost: 0.0,
+            contribution: Contribution::default(),
+            adjustment: 0.0,
+        }
+    }
+
+    pub fn attend_all(&mut self, days: &[Day]) {
+        for (a, d) in self.attendance.iter_mut().zip(days.iter()) {
+            a.present = true;
+            a.servings = d.servings.clone();
+        }
+    }
+
+    pub fn copy_attendance(&mut self, attendance: &[Attendance]) {
+        for (a, other) in self.attendance.iter_mut().zip(attendance.iter()) {
+            a.present = other.present;
+            a.servings = other.servings.clone();
+            a.fractions = other.fractions.clone();
+        }
+    }
+
+    pub fn apply_stay(&mut self, days: &[Day]) {
+        let stay = match self.stay {
+            Some(stay) => stay,
+            None => return,
+        };
+        for (idx, (a, d)) in self.attendance.iter_mut().zip(days.iter()).enumerate() {
+            a.servings.breakfast =
+                d.servings.breakfast && stay.includes(idx, ServingType::Breakfast);
+            a.servings.lunch = d.servings.lunch && stay.includes(idx, ServingType::Lunch);
+            a.servings.dinner = d.servings.dinner && stay.includes(idx, ServingType::Dinner);
+            a.servings.snacks = d.servings.snacks && stay.includes(idx, ServingType::Snacks);
+            a.present = idx >= stay.arrival_day && idx <= stay.departure_day;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Contribution, Day, Expense, ServingType};
+
+    fn assert_costs(people: &[(Contribution, f64)], expected: &[f64], expected_remainder: f64) {
+        let (costs, remainder) = Contribution::settle(people);
+        for (cost, expected) in costs.iter().zip(expected.iter()) {
+            assert!(
+                (cost - expected).abs() < 1e-6,
+                "{:?} != {:?}",
+                costs,
+                expected
+            );
         }
+        assert!((remainder - expected_remainder).abs() < 1e-6);
+    }
+
+    #[test]
+    fn fixed_remainder_follows_shares() {
+        assert_costs(
+            &[
+                (Contribution::Fixed(50.0), 80.0),
+                (Contribution::Shared, 40.0),
+                (Contribution::Shared, 40.0),
+            ],
+            &[50.0, 55.0, 55.0],
+            0.0,
+        );
+    }
+
+    #[test]
+    fn fixed_above_share_lowers_others() {
+        assert_costs(
+            &[
+                (Contribution::Fixed(60.0), 30.0),
+                (Contribution::Shared, 10.0),
+                (Contribution::Shared, 30.0),
+            ],
+            &[60.0, 2.5, 7.5],
+            0.0,
+        );
+    }
+
+    #[test]
+    fn capped_pays_share_up_to_cap() {
+        assert_costs(
+            &[
+                (Contribution::Capped(20.0), 50.0),
+                (Contribution::Shared, 25.0),
+                (Contribution::Shared, 75.0),
+            ],
+            &[20.0, 32.5, 97.5],
+            0.0,
+        );
+        assert_costs(
+            &[
+                (Contribution::Capped(100.0), 30.0),
+                (Contribution::Shared, 30.0),
+            ],
+            &[30.0, 30.0],
+            0.0,
+        );
+    }
+
+    #[test]
+    fn capped_under_cap_takes_part_of_remainder() {
+        assert_costs(
+            &[
+                (Contribution::Fixed(0.0), 30.0),
+                (Contribution::Capped(100.0), 30.0),
+                (Contribution::Shared, 30.0),
+            ],
+            &[0.0, 45.0, 45.0],
+            0.0,
+        );
+    }
+
+    #[test]
+    fn mixed_group_repeats_until_caps_are_full() {
+        assert_costs(
+            &[
+                (Contribution::Fixed(0.0), 60.0),
+                (Contribution::Capped(40.0), 30.0),
+                (Contribution::Shared, 30.0),
+            ],
+            &[0.0, 40.0, 80.0],
+            0.0,
+        );
+    }
+
+    #[test]
+    fn remainder_without_receivers_is_reported() {
+        assert_costs(
+            &[
+                (Contribution::Fixed(10.0), 30.0),
+                (Contribution::Capped(25.0), 20.0),
+            ],
+            &[10.0, 25.0],
+            15.0,
+        );
+    }
+
+    fn lunch_only_day(lunch_weight: f64) -> Day {
+        let mut day = Day::new("Day".to_string());
+        day.servings.breakfast = false;
+        day.servings.dinner = false;
+        day.servings.snacks = false;
+        day.lunch_attendance_weight = lunch_weight;
+        day
+    }
+
+    #[test]
+    fn charged_servings_fall_back_to_eaten_servings() {
+        let mut expense = Expense::new(1, "Pizza".to_string(), 80.0, "EUR".to_string());
+        expense.serving_type.dinner = true;
+        let servings = expense.charged_servings(&[lunch_only_day(2.0)]).unwrap();
+        assert!(servings.get(ServingType::Lunch));
+        assert!(!servings.get(ServingType::Dinner));
+    }
+
+    #[test]
+    fn charged_servings_none_when_nobody_eats() {
+        let mut expense = Expense::new(1, "Pizza".to_string(), 80.0, "EUR".to_string());
+        expense.serving_type.lunch = true;
+        assert!(expense.charged_servings(&[lunch_only_day(0.0)]).is_none());
     }
 }